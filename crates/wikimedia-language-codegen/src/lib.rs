@@ -7,7 +7,7 @@ use codegen::{Scope, Variant};
 use regex::Regex;
 use serde_json::Value;
 
-const LANGUAGE_ENUM_NAME: &'static str = "WikiLanguage";
+const LANGUAGE_ENUM_NAME: &str = "WikiLanguage";
 
 #[derive(PartialEq, Eq, Hash)]
 pub enum WikimediaCode {
@@ -18,7 +18,7 @@ pub enum WikimediaCode {
     Wikiquote,
     Wikisource,
     Wikiversity,
-    Wikivoayge,
+    Wikivoyage,
 }
 
 impl WikimediaCode {
@@ -26,12 +26,12 @@ impl WikimediaCode {
         match str {
             "wiki" => Some(Self::Wikipedia),
             "wiktionary" => Some(Self::Wiktionary),
-            "wikibooks" => Some(Self::Wiktionary),
+            "wikibooks" => Some(Self::Wikibooks),
             "wikinews" => Some(Self::Wikinews),
             "wikiquote" => Some(Self::Wikiquote),
             "wikisource" => Some(Self::Wikisource),
             "wikiversity" => Some(Self::Wikiversity),
-            "wikivoyage" => Some(Self::Wikivoayge),
+            "wikivoyage" => Some(Self::Wikivoyage),
             _ => None,
         }
     }
@@ -45,7 +45,7 @@ impl WikimediaCode {
             WikimediaCode::Wikiquote => "wikiquote",
             WikimediaCode::Wikisource => "wikisource",
             WikimediaCode::Wikiversity => "wikiversity",
-            WikimediaCode::Wikivoayge => "wikivoyage",
+            WikimediaCode::Wikivoyage => "wikivoyage",
         }
     }
 }
@@ -58,7 +58,7 @@ const WIKIPEDIA_CODE_VARIANTS: [WikimediaCode; 8] = [
     WikimediaCode::Wikiquote,
     WikimediaCode::Wikisource,
     WikimediaCode::Wikiversity,
-    WikimediaCode::Wikivoayge,
+    WikimediaCode::Wikivoyage,
 ];
 
 pub struct LanguageData {
@@ -136,6 +136,9 @@ pub fn languages_as_enum_code(languages: Vec<LanguageData>) -> Scope {
         .derive("Debug")
         .derive("Clone")
         .derive("Copy")
+        .derive("PartialEq")
+        .derive("Eq")
+        .derive("Hash")
         .doc("All languages in the Wikimedia Project's specification\n\nGenerated by 'wikimedia-language-codegen'");
 
     languages.iter().for_each(|language_data| {
@@ -244,7 +247,7 @@ impl LanguageData {
         Some(format!(
             "{} => Some(\"{}\"),\n",
             self.enum_variant(),
-            self.codes.get(&wikimedia_code)?
+            self.codes.get(wikimedia_code)?
         ))
     }
 
@@ -29,13 +29,14 @@ fn main() {
     let dir_path = path.clone();
 
     if !dir_path.exists() {
-        DirBuilder::new().create(dir_path.as_path()).expect(
-            format!(
-                "Failed to create output directory at {}",
-                dir_path.display()
-            )
-            .as_str(),
-        )
+        DirBuilder::new()
+            .create(dir_path.as_path())
+            .unwrap_or_else(|_| {
+                panic!(
+                    "Failed to create output directory at {}",
+                    dir_path.display()
+                )
+            });
     }
 
     path.push("wikimedia_languages.rs");
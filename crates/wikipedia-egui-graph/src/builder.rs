@@ -1,4 +1,5 @@
 use std::{
+    collections::{HashMap, HashSet},
     fmt::Display,
     sync::{Arc, Mutex},
 };
@@ -10,12 +11,15 @@ use crate::{
 use egui_graphs::{Graph, SettingsInteraction, SettingsNavigation};
 use fastrand::Rng;
 use petgraph::prelude::StableDiGraph;
-use wikipedia_graph::{HeaderMap, WikiLanguage, WikipediaClient, WikipediaClientConfig};
+use wikipedia_graph::{
+    HeaderMap, PageCache, WikiLanguage, WikiProject, WikipediaClient, WikipediaClientConfig,
+};
 
 // Don't worry, I might add more
 pub struct WikipediaGraphAppBuilder {
     client_config: WikipediaClientConfig,
     language: WikiLanguage,
+    project: WikiProject,
 }
 
 impl Default for WikipediaGraphAppBuilder {
@@ -26,6 +30,7 @@ impl Default for WikipediaGraphAppBuilder {
                 .user_agent(USER_AGENT)
                 .expect("User agent is invalid"),
             language: WikiLanguage::from_code("en").expect("Language 'en' doesn't exist"),
+            project: WikiProject::Wikipedia,
         }
     }
 }
@@ -35,6 +40,15 @@ impl WikipediaGraphAppBuilder {
         Self {
             language,
             client_config: self.client_config.language(language),
+            ..self
+        }
+    }
+
+    pub fn with_project(self, project: WikiProject) -> Self {
+        Self {
+            project,
+            client_config: self.client_config.project(project),
+            ..self
         }
     }
 
@@ -49,6 +63,13 @@ impl WikipediaGraphAppBuilder {
         })
     }
 
+    pub fn with_cache(self, cache: impl PageCache + 'static) -> Self {
+        Self {
+            client_config: self.client_config.cache(cache),
+            ..self
+        }
+    }
+
     pub fn headers(&self) -> &HeaderMap {
         self.client_config.headers()
     }
@@ -100,8 +121,12 @@ impl WikipediaGraphAppBuilder {
             style_settings: StyleSettings::default(),
             initialization: 5,
             language: self.language,
+            project: self.project,
             search_data: SearchData::default(),
             node_stores: Arc::new(Mutex::new(Vec::new())),
+            requested_summaries: HashSet::new(),
+            summary_errors: HashMap::new(),
+            thumbnails: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 }
@@ -12,24 +12,31 @@ use egui_graphs::{
 use fastrand::Rng;
 use log::warn;
 use petgraph::graph::NodeIndex;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 use web_time::{Duration, Instant};
-use wikipedia_graph::{HttpError, Url, WikipediaGraph, WikipediaPage};
+use wikipedia_graph::{HttpError, SectionFilter, Url, WikipediaGraph, WikipediaPage};
 
-pub use wikipedia_graph::{WikiLanguage, WikipediaClient};
+pub use wikipedia_graph::{WikiLanguage, WikiProject, WikipediaClient};
+
+/// Responses waiting to be applied to the graph on the next frame
+pub type NodeStore<T> = Arc<Mutex<Vec<(NodeIndex, Result<T, HttpError>, NodeAction)>>>;
+
+/// Thumbnail downloads by URL, holding `None` until the download finishes
+pub type ThumbnailStore = Arc<Mutex<HashMap<Url, Option<Result<String, String>>>>>;
 
 fn store_callback_vec<T>(
-    data: Arc<Mutex<Vec<(NodeIndex, Result<T, HttpError>, NodeAction)>>>,
+    data: NodeStore<T>,
     index: NodeIndex,
     action: NodeAction,
 ) -> impl Fn(Result<T, HttpError>) + Clone {
     move |response| match data.lock() {
         Ok(mut data) => {
-            data.push((index, response, action));
+            data.push((index, response, action.clone()));
         }
         Err(mut e) => {
             warn!("Waiting on mutex...");
-            e.get_mut().push((index, response, action));
+            e.get_mut().push((index, response, action.clone()));
         }
     }
 }
@@ -53,8 +60,12 @@ pub struct WikipediaGraphApp {
     pub style_settings: StyleSettings,
     pub initialization: u8,
     pub language: WikiLanguage,
+    pub project: WikiProject,
     pub search_data: SearchData,
-    pub node_stores: Arc<Mutex<Vec<(NodeIndex, Result<WikipediaPage, HttpError>, NodeAction)>>>,
+    pub node_stores: NodeStore<WikipediaPage>,
+    pub requested_summaries: HashSet<NodeIndex>,
+    pub summary_errors: HashMap<NodeIndex, String>,
+    pub thumbnails: ThumbnailStore,
 }
 
 pub struct FrameCounter {
@@ -127,6 +138,17 @@ impl Default for ControlSettings {
 #[derive(Default)]
 pub struct NodeEditor {
     page_title: String,
+    language_codes: String,
+}
+
+impl NodeEditor {
+    /// The languages listed by their codes, like `de, fr`, skipping codes that aren't languages
+    fn languages(&self) -> Vec<WikiLanguage> {
+        self.language_codes
+            .split([',', ' '])
+            .filter_map(WikiLanguage::from_code)
+            .collect()
+    }
 }
 
 pub struct StyleSettings {
@@ -209,9 +231,13 @@ impl Default for SearchData {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub enum NodeAction {
     Expand,
+    ExpandLead,
+    ExpandIncoming,
+    ExpandLanguages(Vec<WikiLanguage>),
+    Summary,
     None,
 }
 
@@ -222,6 +248,10 @@ impl WikipediaGraphApp {
         Self { language, ..self }
     }
 
+    pub fn with_project(self, project: WikiProject) -> Self {
+        Self { project, ..self }
+    }
+
     pub fn new(_: &CreationContext<'_>) -> Self {
         WikipediaGraphAppBuilder::default().build()
     }
@@ -229,9 +259,10 @@ impl WikipediaGraphApp {
 
 impl WikipediaGraphApp {
     pub fn update_nodes_from_store(
-        store: &mut Arc<Mutex<Vec<(NodeIndex, Result<WikipediaPage, HttpError>, NodeAction)>>>,
+        store: &mut NodeStore<WikipediaPage>,
         graph: &mut Graph<WikipediaPage>,
         rng: &mut Rng,
+        summary_errors: &mut HashMap<NodeIndex, String>,
     ) {
         match store.try_lock() {
             Ok(mut store) => {
@@ -243,11 +274,29 @@ impl WikipediaGraphApp {
                         Ok(t) => Some((index, t, action)),
                         Err(e) => {
                             warn!("Request failed: {e}");
+
+                            if let NodeAction::Summary = action {
+                                summary_errors.insert(index, e.to_string());
+                            }
+
                             None
                         }
                     })
                     .for_each(|(index, page, action)| match graph.node_mut(index) {
                         Some(node) => {
+                            // Summaries are loaded separately from the body, so neither replaces the other
+                            let page = match (
+                                &action,
+                                page.try_get_summary(),
+                                node.payload().try_get_summary(),
+                            ) {
+                                (NodeAction::Summary, Some(summary), _) => {
+                                    node.payload().clone().with_summary(summary.clone())
+                                }
+                                (_, None, Some(summary)) => page.with_summary(summary.clone()),
+                                _ => page,
+                            };
+
                             node.set_label(page.title());
                             *node.payload_mut() = page;
 
@@ -255,7 +304,18 @@ impl WikipediaGraphApp {
                                 NodeAction::Expand => {
                                     Self::expand_node_with_graph(graph, rng, index);
                                 }
-                                NodeAction::None => {}
+                                NodeAction::ExpandLead => {
+                                    Self::expand_node_lead_with_graph(graph, rng, index);
+                                }
+                                NodeAction::ExpandIncoming => {
+                                    Self::expand_node_incoming_with_graph(graph, rng, index);
+                                }
+                                NodeAction::ExpandLanguages(languages) => {
+                                    Self::expand_node_languages_with_graph(
+                                        graph, rng, index, &languages,
+                                    );
+                                }
+                                NodeAction::Summary | NodeAction::None => {}
                             }
                         }
                         None => warn!(
@@ -278,7 +338,49 @@ impl WikipediaGraphApp {
         rng: &mut Rng,
         index: NodeIndex,
     ) {
-        match graph.try_expand_node(index) {
+        let indicies = graph.try_expand_node(index);
+
+        Self::place_new_nodes(graph, rng, index, indicies);
+    }
+
+    pub fn expand_node_lead_with_graph(
+        graph: &mut Graph<WikipediaPage>,
+        rng: &mut Rng,
+        index: NodeIndex,
+    ) {
+        let indicies = graph.try_expand_node_with(index, &SectionFilter::lead());
+
+        Self::place_new_nodes(graph, rng, index, indicies);
+    }
+
+    pub fn expand_node_incoming_with_graph(
+        graph: &mut Graph<WikipediaPage>,
+        rng: &mut Rng,
+        index: NodeIndex,
+    ) {
+        let indicies = graph.try_expand_node_incoming(index);
+
+        Self::place_new_nodes(graph, rng, index, indicies);
+    }
+
+    pub fn expand_node_languages_with_graph(
+        graph: &mut Graph<WikipediaPage>,
+        rng: &mut Rng,
+        index: NodeIndex,
+        languages: &[WikiLanguage],
+    ) {
+        let indicies = graph.try_expand_node_languages(index, languages);
+
+        Self::place_new_nodes(graph, rng, index, indicies);
+    }
+
+    fn place_new_nodes(
+        graph: &mut Graph<WikipediaPage>,
+        rng: &mut Rng,
+        index: NodeIndex,
+        indicies: Option<Vec<NodeIndex>>,
+    ) {
+        match indicies {
             Some(indicies) => {
                 let parent_pos = graph
                     .node(index)
@@ -374,6 +476,8 @@ impl WikipediaGraphApp {
 
     fn remove_node(&mut self, index: NodeIndex) {
         self.graph.remove_node(index);
+        self.requested_summaries.remove(&index);
+        self.summary_errors.remove(&index);
     }
 
     fn update_position_from_meta(&mut self, meta: &mut MetadataFrame) {
@@ -393,18 +497,122 @@ impl WikipediaGraphApp {
     }
 
     fn url_of_page(&self, page: &WikipediaPage) -> Url {
-        page.url_with_lang(self.language)
-            .expect("Selected language has no iso 639-1 encoding")
+        page.url()
+            .expect("Page language has no edition of the page's project")
     }
 
     pub fn expand_connected_nodes(&mut self, index: NodeIndex) {
-        for index in Self::connected_nodes(&self.graph, index, petgraph::Direction::Outgoing)
-            .collect::<Vec<_>>()
+        let (indicies, pages): (Vec<NodeIndex>, Vec<WikipediaPage>) =
+            Self::connected_nodes(&self.graph, index, petgraph::Direction::Outgoing)
+                .filter_map(|index| Some((index, self.graph.node(index)?.payload().clone())))
+                .unzip();
+
+        let node_stores = self.node_stores.clone();
+
+        // All the children are loaded together through the links API whatever the client's url type,
+        // which only takes one request per batch of pages. Expanding only the lead loads its page again with the client's url type
+        if let Err(e) = WikipediaPage::load_many(&pages, &self.client, move |responses| {
+            for (index, response) in indicies.iter().zip(responses) {
+                store_callback_vec(node_stores.clone(), *index, NodeAction::Expand)(response);
+            }
+        }) {
+            warn!("{e}")
+        }
+    }
+
+    pub fn load_incoming_nodes(&mut self, index: NodeIndex) {
+        if let Some(node) = self.graph.node(index)
+            && let Err(e) = node.payload().load_backlinks(
+                &self.client,
+                store_callback_vec(self.node_stores.clone(), index, NodeAction::ExpandIncoming),
+            )
+        {
+            warn!("{e}")
+        }
+    }
+
+    pub fn load_langlinks(&mut self, index: NodeIndex, languages: Vec<WikiLanguage>) {
+        if let Some(node) = self.graph.node(index)
+            && let Err(e) = node.payload().load_langlinks(
+                &self.client,
+                store_callback_vec(
+                    self.node_stores.clone(),
+                    index,
+                    NodeAction::ExpandLanguages(languages),
+                ),
+            )
+        {
+            warn!("{e}")
+        }
+    }
+
+    pub fn load_summary(&mut self, index: NodeIndex) {
+        if self.requested_summaries.insert(index)
+            && let Some(node) = self.graph.node(index)
+            && let Err(e) = node.payload().load_summary(
+                &self.client,
+                store_callback_vec(self.node_stores.clone(), index, NodeAction::Summary),
+            )
         {
-            self.expand_node(index);
+            warn!("{e}");
+
+            self.summary_errors.insert(index, e.to_string());
         }
     }
 
+    /// Forgets a failed summary so it's requested again
+    pub fn retry_summary(&mut self, index: NodeIndex) {
+        self.requested_summaries.remove(&index);
+        self.summary_errors.remove(&index);
+    }
+
+    /// Starts downloading a thumbnail the first time it's asked for
+    ///
+    /// Returns the image URI once the download finishes, or the reason it failed
+    pub fn load_thumbnail(&mut self, ctx: &Context, url: &Url) -> Option<Result<String, String>> {
+        let mut thumbnails = match self.thumbnails.lock() {
+            Ok(thumbnails) => thumbnails,
+            Err(e) => {
+                warn!("Waiting on mutex...");
+                e.into_inner()
+            }
+        };
+
+        if let Some(thumbnail) = thumbnails.get(url) {
+            return thumbnail.clone();
+        }
+
+        thumbnails.insert(url.clone(), None);
+
+        egui_extras::install_image_loaders(ctx);
+
+        let (store, ctx, url) = (self.thumbnails.clone(), ctx.clone(), url.clone());
+
+        ehttp::fetch(ehttp::Request::get(url.as_str()), move |response| {
+            let thumbnail = match response {
+                Ok(response) if response.ok => {
+                    // Keeping the extension at the end lets the loader pick a decoder
+                    let uri = format!("bytes://{url}");
+
+                    ctx.include_bytes(uri.clone(), response.bytes);
+
+                    Ok(uri)
+                }
+                Ok(response) => Err(format!("{} {}", response.status, response.status_text)),
+                Err(e) => Err(e),
+            };
+
+            match store.lock() {
+                Ok(mut store) => store.insert(url, Some(thumbnail)),
+                Err(mut e) => e.get_mut().insert(url, Some(thumbnail)),
+            };
+
+            ctx.request_repaint();
+        });
+
+        None
+    }
+
     pub fn load_node(&mut self, index: NodeIndex, action: NodeAction) {
         if let Some(node) = self.graph.node(index)
             && let Err(e) = node.payload().load_page_text(
@@ -419,7 +627,12 @@ impl WikipediaGraphApp {
 
 impl App for WikipediaGraphApp {
     fn update(&mut self, ctx: &Context, _: &mut eframe::Frame) {
-        Self::update_nodes_from_store(&mut self.node_stores, &mut self.graph, &mut self.rng);
+        Self::update_nodes_from_store(
+            &mut self.node_stores,
+            &mut self.graph,
+            &mut self.rng,
+            &mut self.summary_errors,
+        );
 
         self.frame_counter.update_fps();
 
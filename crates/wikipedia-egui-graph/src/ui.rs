@@ -235,6 +235,10 @@ impl WikipediaGraphApp {
             self.set_selected_node(None);
 
             self.graph.g_mut().clear();
+
+            self.requested_summaries.clear();
+
+            self.summary_errors.clear();
         }
 
         let node_editor = &mut self.node_editor;
@@ -244,7 +248,9 @@ impl WikipediaGraphApp {
         );
 
         if ui.button("Create/Select node").clicked() {
-            let page = WikipediaPage::from_title(&node_editor.page_title);
+            let page = WikipediaPage::from_title(&node_editor.page_title)
+                .with_project(self.project)
+                .with_language(self.language);
             let index = if let Some(index) = <egui_graphs::Graph<WikipediaPage> as WikipediaGraph<
                 NodeIndex,
             >>::node_exists_with_value(
@@ -303,9 +309,64 @@ impl WikipediaGraphApp {
 
                 let page_text_loaded = page.is_page_text_loaded();
 
+                let redirects = page.redirects().join(", ");
+
+                let summary = page.try_get_summary().cloned();
+
+                let summary_error = self.summary_errors.get(&index).cloned();
+
+                if summary.is_none() {
+                    self.load_summary(index);
+                }
+
                 egui::ScrollArea::vertical().show(ui, |ui| {
                     ui.label(RichText::new(title).size(30.0));
 
+                    match &summary {
+                        Some(summary) => {
+                            if let Some(description) = summary.description() {
+                                ui.label(RichText::new(description).italics());
+                            }
+
+                            if !summary.extract().is_empty() {
+                                ui.label(summary.extract());
+                            }
+
+                            if let Some(thumbnail) = summary.thumbnail() {
+                                match self.load_thumbnail(ui.ctx(), thumbnail) {
+                                    Some(Ok(uri)) => {
+                                        ui.add(
+                                            egui::Image::new(uri)
+                                                .max_width(ui.available_width())
+                                                .corner_radius(4.0),
+                                        );
+                                    }
+                                    Some(Err(e)) => {
+                                        ui.colored_label(
+                                            Color32::RED,
+                                            format!("Thumbnail failed to load: {e}"),
+                                        );
+                                    }
+                                    None => {
+                                        ui.add(Spinner::new());
+                                    }
+                                }
+                            }
+                        }
+                        None => match &summary_error {
+                            Some(e) => {
+                                ui.colored_label(Color32::RED, format!("Summary failed to load: {e}"));
+
+                                if ui.button("Retry").clicked() {
+                                    self.retry_summary(index);
+                                }
+                            }
+                            None => {
+                                ui.add(Spinner::new());
+                            }
+                        },
+                    }
+
                     ui.hyperlink_to(
                         "Wikipedia Page",
                         self.url_of(index).expect("Selected node doesn't exist"),
@@ -315,6 +376,29 @@ impl WikipediaGraphApp {
                         self.expand_node(index);
                     }
 
+                    if ui
+                        .button("Expand lead only")
+                        .on_hover_text("Only add the pages linked before the first heading")
+                        .clicked()
+                    {
+                        self.load_node(index, crate::NodeAction::ExpandLead);
+                    }
+
+                    ui.collapsing("Other Languages", |ui| {
+                        ui.add(
+                            TextEdit::singleline(&mut self.node_editor.language_codes)
+                                .hint_text("Language codes, like 'de, fr'"),
+                        );
+
+                        let button = ui.button("Show in other languages").on_hover_text(
+                            "Add this page on the wikis of the listed languages, or of every language if none are listed",
+                        );
+
+                        if button.clicked() {
+                            self.load_langlinks(index, self.node_editor.languages());
+                        }
+                    });
+
                     if ui.button("Remove node").clicked() {
                         self.remove_selected();
                     }
@@ -324,6 +408,10 @@ impl WikipediaGraphApp {
                     ui.collapsing("Struct data", |ui| {
                         ui.label(format!("pathinfo: {}", pathinfo));
                         ui.label(format!("page text loaded: {}", page_text_loaded));
+
+                        if !redirects.is_empty() {
+                            ui.label(format!("redirected from: {}", redirects));
+                        }
                     });
 
                     ui.separator();
@@ -333,6 +421,14 @@ impl WikipediaGraphApp {
                     });
 
                     ui.collapsing("Incoming Nodes", |ui| {
+                        let button = ui
+                            .button("Load incoming nodes")
+                            .on_hover_text("Add every article that links to this page");
+
+                        if button.clicked() {
+                            self.load_incoming_nodes(index);
+                        }
+
                         self.connected_nodes_ui(ui, index, petgraph::Direction::Incoming);
                     });
 
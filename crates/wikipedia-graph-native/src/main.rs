@@ -1,6 +1,10 @@
 use eframe::{NativeOptions, run_native};
+use std::time::Duration;
 use wikipedia_egui_graph::builder::WikipediaGraphAppBuilder;
-use wikipedia_graph::WikiLanguage;
+use wikipedia_graph::{DirectoryCache, WikiLanguage};
+
+/// The most pages kept in the page cache, so the temporary directory doesn't grow forever
+const MAX_CACHED_PAGES: usize = 5000;
 
 fn main() {
     pretty_env_logger::init();
@@ -15,6 +19,18 @@ fn main() {
         Box::new(move |_cc| {
             let mut app_builder = WikipediaGraphAppBuilder::default();
 
+            // Pages are kept for a day, so relaunching the app doesn't refetch everything
+            match DirectoryCache::new(std::env::temp_dir().join("wikipedia-graph-cache")) {
+                Ok(cache) => {
+                    app_builder = app_builder.with_cache(
+                        cache
+                            .with_ttl(Duration::from_secs(60 * 60 * 24))
+                            .with_max_entries(MAX_CACHED_PAGES),
+                    )
+                }
+                Err(e) => log::warn!("Failed to create the page cache: {e}"),
+            }
+
             if let Some(lang) = args.next() {
                 if let Some(language) = WikiLanguage::from_code(lang.as_str()) {
                     app_builder = app_builder.with_language(language)
@@ -0,0 +1,189 @@
+use super::HttpError;
+use crate::WikipediaBody;
+use crate::response::{Query, QueryPage, QueryResponse, TitleMapping};
+use std::collections::{BTreeMap, HashMap};
+
+/// The most titles the API accepts in a single query
+pub const MAX_BATCH_TITLES: usize = 50;
+
+/// The body of a page from a batch, along with the titles it was redirected from
+pub(crate) type BatchResult = Result<(WikipediaBody, Vec<String>), HttpError>;
+
+/// Turn a list of title mappings from a query response into a map
+pub(crate) fn title_map(mappings: &[TitleMapping]) -> HashMap<&str, &str> {
+    mappings
+        .iter()
+        .map(|mapping| (mapping.from.as_str(), mapping.to.as_str()))
+        .collect()
+}
+
+/// Get the error for a page the API marked as missing or invalid
+pub(crate) fn page_error(page: &QueryPage) -> Option<HttpError> {
+    if page.invalid {
+        return Some(HttpError::InvalidTitle(
+            page.invalidreason.clone().unwrap_or_default(),
+        ));
+    }
+
+    page.missing.then_some(HttpError::MissingTitle)
+}
+
+/// Give every title in a batch the same error
+pub(crate) fn all_failed(titles: &[String], err: &HttpError) -> Vec<(String, BatchResult)> {
+    titles
+        .iter()
+        .map(|title| (title.clone(), Err(err.clone())))
+        .collect()
+}
+
+/// Get the titles a body from [split_batch] was redirected from, in the order they were followed
+pub(crate) fn redirected_from(body: &WikipediaBody) -> Vec<String> {
+    match body {
+        WikipediaBody::Links(response) => response.query.as_ref().map_or_else(Vec::new, |query| {
+            query
+                .redirects
+                .iter()
+                .map(|mapping| mapping.from.clone())
+                .collect()
+        }),
+        _ => Vec::new(),
+    }
+}
+
+/// Split the response of a multi-title links query into a body for each requested title
+///
+/// Each body has the same shape as the response for a single title, so it can be used like any other [WikipediaBody::Links]
+pub(crate) fn split_batch(
+    titles: &[String],
+    response: &QueryResponse,
+) -> Vec<(String, BatchResult)> {
+    let query = match response.query() {
+        Ok(query) => query,
+        Err(err) => return all_failed(titles, &err.into()),
+    };
+
+    let normalized = title_map(&query.normalized);
+    let redirects = title_map(&query.redirects);
+
+    let pages: HashMap<&str, (&String, _)> = query
+        .pages
+        .iter()
+        .flatten()
+        .map(|(id, page)| (page.title.as_str(), (id, page)))
+        .collect();
+
+    titles
+        .iter()
+        .map(|title| {
+            let title_normalized = normalized
+                .get(title.as_str())
+                .copied()
+                .unwrap_or(title.as_str());
+
+            // The API lists every redirect of a chain, like `UK -> U.K. -> United Kingdom`
+            let mut resolved = title_normalized;
+            let mut chain: Vec<TitleMapping> = Vec::new();
+
+            while let Some(target) = redirects.get(resolved) {
+                if chain.iter().any(|mapping| mapping.from == resolved) {
+                    break;
+                }
+
+                chain.push(TitleMapping {
+                    from: resolved.to_string(),
+                    to: (*target).to_string(),
+                });
+                resolved = target;
+            }
+
+            let redirected_from = chain.iter().map(|mapping| mapping.from.clone()).collect();
+
+            let result = match pages.get(resolved) {
+                Some((_, page)) if let Some(err) = page_error(page) => Err(err),
+                Some((id, page)) => {
+                    let response = QueryResponse {
+                        query: Some(Query {
+                            // Kept so a cached body still knows which titles it was redirected from
+                            redirects: chain.clone(),
+                            pages: Some(BTreeMap::from([((*id).clone(), (*page).clone())])),
+                            ..Query::default()
+                        }),
+                        ..QueryResponse::default()
+                    };
+
+                    Ok((WikipediaBody::Links(response), redirected_from))
+                }
+                None => Err(HttpError::NoPageBody),
+            };
+
+            (title.clone(), result)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn split_batch_response() {
+        let titles = vec![
+            String::from("Waffle"),
+            String::from("USA"),
+            String::from("belgian_waffle"),
+            String::from("Not a page"),
+            String::from("UK"),
+        ];
+
+        let body: QueryResponse = serde_json::from_value(serde_json::json!({
+            "query": {
+                "normalized": [{ "from": "belgian_waffle", "to": "Belgian waffle" }],
+                "redirects": [
+                    { "from": "USA", "to": "United States" },
+                    { "from": "UK", "to": "U.K." },
+                    { "from": "U.K.", "to": "United Kingdom" }
+                ],
+                "pages": {
+                    "1": { "pageid": 1, "ns": 0, "title": "Waffle", "links": [{ "ns": 0, "title": "Batter" }] },
+                    "2": { "pageid": 2, "ns": 0, "title": "United States", "links": [] },
+                    "3": { "pageid": 3, "ns": 0, "title": "Belgian waffle" },
+                    "-1": { "ns": 0, "title": "Not a page", "missing": "" },
+                    "4": { "pageid": 4, "ns": 0, "title": "United Kingdom" }
+                }
+            }
+        }))
+        .unwrap();
+
+        let results = split_batch(&titles, &body);
+
+        assert_eq!(results.len(), 5);
+
+        let (title, waffle) = &results[0];
+        let (body, redirects) = waffle.as_ref().unwrap();
+
+        assert_eq!(title, "Waffle");
+        assert!(redirects.is_empty());
+        assert_eq!(body.get_pathinfo().unwrap(), "Waffle");
+        assert_eq!(
+            body.get_linked_pages().unwrap().next().unwrap().title(),
+            "Batter"
+        );
+
+        let (body, redirects) = results[1].1.as_ref().unwrap();
+
+        assert_eq!(body.get_pathinfo().unwrap(), "United States");
+        assert_eq!(redirects, &["USA"]);
+
+        let (body, _) = results[2].1.as_ref().unwrap();
+
+        assert_eq!(body.get_pathinfo().unwrap(), "Belgian waffle");
+
+        assert!(matches!(results[3].1, Err(HttpError::MissingTitle)));
+
+        let (body, redirects) = results[4].1.as_ref().unwrap();
+
+        assert_eq!(body.get_pathinfo().unwrap(), "United Kingdom");
+        assert_eq!(redirects, &["UK", "U.K."]);
+        assert_eq!(redirected_from(body), ["UK", "U.K."]);
+    }
+}
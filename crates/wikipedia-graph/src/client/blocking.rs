@@ -0,0 +1,89 @@
+use super::{HttpError, RefreshReport, WikipediaClient, WikipediaClientConfig};
+use crate::{WikipediaBody, WikipediaPage};
+use std::fmt::Display;
+
+/// A client that blocks the current thread until each request completes
+///
+/// This wraps a [WikipediaClient], so URL construction and body parsing are shared with it
+///
+/// *This struct requires the `blocking` feature, and is unavailable on wasm32*
+///
+/// # Example
+/// ```no_run
+/// # use wikipedia_graph::{BlockingWikipediaClient, WikipediaPage};
+/// # fn main() -> Result< (), Box<dyn std::error::Error>> {
+/// let client = BlockingWikipediaClient::default();
+///
+/// let page = client.load_page_text(&WikipediaPage::from_title("Waffle"))?;
+///
+/// for page in page.try_get_linked_pages().unwrap() {
+///     println!("Connects to {}", page.title());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct BlockingWikipediaClient {
+    client: WikipediaClient,
+}
+
+impl BlockingWikipediaClient {
+    /// Create a [BlockingWikipediaClient] from a [WikipediaClientConfig]
+    pub fn from_config(config: WikipediaClientConfig) -> Self {
+        Self {
+            client: WikipediaClient::from_config(config),
+        }
+    }
+
+    /// Get the underlying [WikipediaClient]
+    pub fn client(&self) -> &WikipediaClient {
+        &self.client
+    }
+
+    /// Get the wikipedia page at the specified pathinfo, following redirects
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the http request failed
+    pub fn get<T: Display>(&self, pathinfo: T) -> Result<WikipediaBody, HttpError> {
+        pollster::block_on(self.client.get_async(pathinfo))
+    }
+
+    /// Get a random and unloaded page using the Wikimedia API
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the request failed
+    pub fn random_page(&self) -> Result<WikipediaPage, HttpError> {
+        pollster::block_on(self.client.random_page_async())
+    }
+
+    /// Load the text of a page
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the request for the page data fails
+    pub fn load_page_text(&self, page: &WikipediaPage) -> Result<WikipediaPage, HttpError> {
+        pollster::block_on(page.load_page_text_async(&self.client))
+    }
+
+    /// Check if loaded pages changed since they were loaded, and load the pages that did again
+    pub fn refresh(&self, pages: &[WikipediaPage]) -> RefreshReport {
+        pollster::block_on(self.client.refresh_async(pages))
+    }
+
+    /// Get the contents of the page 'https://en.wikipedia.org/w/api.php', can be used as a network test
+    ///
+    /// # Errors
+    ///
+    /// The method fails if the request fails
+    pub fn get_api_base(&self) -> Result<(), HttpError> {
+        pollster::block_on(self.client.get_api_base_async())
+    }
+}
+
+impl From<WikipediaClient> for BlockingWikipediaClient {
+    fn from(client: WikipediaClient) -> Self {
+        Self { client }
+    }
+}
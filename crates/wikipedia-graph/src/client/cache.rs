@@ -0,0 +1,492 @@
+use super::future::lock;
+use crate::{Title, WikiLanguage, WikiProject, WikipediaBody, WikipediaUrlType};
+use std::collections::HashMap;
+use std::fmt::{Debug, Display};
+use std::sync::{Arc, Mutex};
+use web_time::{Duration, Instant};
+
+/// The key a [PageCache] stores a page body under
+///
+/// Titles are normalized with [Title], so `multekrem`, `Multekrem` and ` Multekrem_` all share the same key
+///
+/// Links bodies are cut short by [crate::WikipediaClientConfig::max_links], so the limit is part of the key
+///
+/// *This struct requires the `client` feature*
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    project: WikiProject,
+    language: String,
+    url_type: WikipediaUrlType,
+    title: String,
+    max_links: Option<usize>,
+}
+
+impl CacheKey {
+    /// Create the key for a page with the wiki and kind of URL it was requested from
+    pub fn new(
+        project: WikiProject,
+        language: WikiLanguage,
+        url_type: WikipediaUrlType,
+        title: &str,
+    ) -> Self {
+        let title = Title::for_project(title, project).to_string();
+
+        Self {
+            project,
+            language: project
+                .language_code(&language)
+                .map_or_else(|| format!("{language:?}"), ToString::to_string),
+            url_type,
+            title,
+            max_links: None,
+        }
+    }
+
+    /// Sets the most links the stored body was cut down to
+    ///
+    /// The default value is `None`
+    pub fn with_max_links(self, max_links: Option<usize>) -> Self {
+        Self { max_links, ..self }
+    }
+
+    /// The project of the wiki the page is on
+    pub fn project(&self) -> WikiProject {
+        self.project
+    }
+
+    /// The language code of the page on its project
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// The kind of URL the page was requested from
+    pub fn url_type(&self) -> WikipediaUrlType {
+        self.url_type
+    }
+
+    /// The normalized title of the page
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    /// The most links the stored body was cut down to
+    pub fn max_links(&self) -> Option<usize> {
+        self.max_links
+    }
+}
+
+impl Display for CacheKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.project {
+            // Wikipedia keys are written without the project, like they were before there were other projects
+            WikiProject::Wikipedia => {
+                write!(f, "{}/{:?}/{}", self.language, self.url_type, self.title)?;
+            }
+            project => write!(
+                f,
+                "{}.{}/{:?}/{}",
+                self.language,
+                project.name().to_lowercase(),
+                self.url_type,
+                self.title
+            )?,
+        }
+
+        match self.max_links {
+            Some(max_links) => write!(f, "?max_links={max_links}"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A store of page bodies that a [crate::WikipediaClient] checks before sending a request
+///
+/// *This trait requires the `client` feature*
+pub trait PageCache: Debug + Send + Sync {
+    /// Get a stored body, or [None] if it is missing or expired
+    fn get(&self, key: &CacheKey) -> Option<WikipediaBody>;
+
+    /// Store a body, replacing any body already stored with the same key
+    fn insert(&self, key: CacheKey, body: WikipediaBody);
+}
+
+/// A cache that keeps page bodies in memory, this also works on wasm32
+///
+/// Clones of a cache share the same pages
+///
+/// *This struct requires the `client` feature*
+#[derive(Clone, Debug, Default)]
+pub struct MemoryCache {
+    entries: Arc<Mutex<HashMap<CacheKey, (Instant, WikipediaBody)>>>,
+    ttl: Option<Duration>,
+    max_entries: Option<usize>,
+}
+
+impl MemoryCache {
+    /// Create an empty cache that never expires or evicts pages
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how long pages are kept before they have to be requested again
+    pub fn with_ttl(self, ttl: Duration) -> Self {
+        Self {
+            ttl: Some(ttl),
+            ..self
+        }
+    }
+
+    /// Sets the most pages kept at once, the oldest pages are evicted first
+    pub fn with_max_entries(self, max_entries: usize) -> Self {
+        Self {
+            max_entries: Some(max_entries),
+            ..self
+        }
+    }
+
+    /// The amount of pages stored, including expired pages that haven't been evicted yet
+    pub fn len(&self) -> usize {
+        lock(&self.entries).len()
+    }
+
+    /// Check if no pages are stored
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl PageCache for MemoryCache {
+    fn get(&self, key: &CacheKey) -> Option<WikipediaBody> {
+        let mut entries = lock(&self.entries);
+
+        let (stored, body) = entries.get(key)?;
+
+        if self.ttl.is_some_and(|ttl| stored.elapsed() > ttl) {
+            entries.remove(key);
+
+            return None;
+        }
+
+        Some(body.clone())
+    }
+
+    fn insert(&self, key: CacheKey, body: WikipediaBody) {
+        let mut entries = lock(&self.entries);
+
+        entries.insert(key, (Instant::now(), body));
+
+        if let Some(max_entries) = self.max_entries {
+            while entries.len() > max_entries {
+                let Some(oldest) = entries
+                    .iter()
+                    .min_by_key(|(_, (stored, _))| *stored)
+                    .map(|(key, _)| key.clone())
+                else {
+                    break;
+                };
+
+                entries.remove(&oldest);
+            }
+        }
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use directory::DirectoryCache;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod directory {
+    use super::{CacheKey, PageCache};
+    use crate::WikipediaBody;
+    use serde::{Deserialize, Serialize};
+    use std::path::{Path, PathBuf};
+    use std::sync::{Arc, Mutex};
+    use web_time::{Duration, SystemTime, UNIX_EPOCH};
+
+    /// A page body as it is stored on disk
+    #[derive(Serialize, Deserialize)]
+    struct CachedBody {
+        /// Seconds since the unix epoch
+        stored: u64,
+        body: WikipediaBody,
+    }
+
+    /// A cache that keeps every page body as a JSON file in a directory, so pages are kept between launches
+    ///
+    /// Files that can't be read are treated as missing
+    ///
+    /// *This struct requires the `client` feature and isn't available on wasm32*
+    #[derive(Clone, Debug)]
+    pub struct DirectoryCache {
+        path: PathBuf,
+        ttl: Option<Duration>,
+        max_entries: Option<usize>,
+        /// How many files are in the directory, counted the first time it's needed
+        entry_count: Arc<Mutex<Option<usize>>>,
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs())
+    }
+
+    impl DirectoryCache {
+        /// Create a cache in the directory, creating the directory if it doesn't exist
+        ///
+        /// # Errors
+        ///
+        /// This method fails if the directory can't be created
+        pub fn new(path: impl Into<PathBuf>) -> std::io::Result<Self> {
+            let path = path.into();
+
+            std::fs::create_dir_all(&path)?;
+
+            Ok(Self {
+                path,
+                ttl: None,
+                max_entries: None,
+                entry_count: Arc::default(),
+            })
+        }
+
+        /// Sets how long pages are kept before they have to be requested again
+        pub fn with_ttl(self, ttl: Duration) -> Self {
+            Self {
+                ttl: Some(ttl),
+                ..self
+            }
+        }
+
+        /// Sets the most pages kept at once, the oldest pages are evicted first
+        pub fn with_max_entries(self, max_entries: usize) -> Self {
+            Self {
+                max_entries: Some(max_entries),
+                ..self
+            }
+        }
+
+        /// The directory the pages are stored in
+        pub fn path(&self) -> &Path {
+            &self.path
+        }
+
+        /// Get the file of a key, hex encoded so every title makes a valid file name
+        ///
+        /// Long keys are cut short and end with a hash of the whole key, since most file systems limit names to 255 bytes
+        fn file_of(&self, key: &CacheKey) -> PathBuf {
+            const MAX_NAME_LENGTH: usize = 200;
+
+            let key = key.to_string();
+
+            let mut name: String = key.bytes().map(|byte| format!("{byte:02x}")).collect();
+
+            if name.len() > MAX_NAME_LENGTH {
+                // FNV-1a, which unlike the standard hasher is the same on every run and version
+                let hash = key.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+                    (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+                });
+
+                name = format!("{}-{hash:016x}", &name[..MAX_NAME_LENGTH - 17]);
+            }
+
+            self.path.join(format!("{name}.json"))
+        }
+
+        fn read(path: &Path) -> Option<CachedBody> {
+            serde_json::from_slice(&std::fs::read(path).ok()?).ok()
+        }
+
+        fn is_expired(&self, cached: &CachedBody) -> bool {
+            self.ttl
+                .is_some_and(|ttl| now().saturating_sub(cached.stored) > ttl.as_secs())
+        }
+
+        /// Remove the oldest files until there are at most `max_entries` left, returning how many files are left
+        ///
+        /// Files are ordered by when they were last written, so the files don't have to be read
+        fn evict(&self, max_entries: usize) -> usize {
+            let Ok(entries) = std::fs::read_dir(&self.path) else {
+                return 0;
+            };
+
+            let mut files: Vec<(SystemTime, PathBuf)> = entries
+                .filter_map(|entry| {
+                    let entry = entry.ok()?;
+                    let path = entry.path();
+
+                    if path.extension()? != "json" {
+                        return None;
+                    }
+
+                    let modified = entry
+                        .metadata()
+                        .and_then(|metadata| metadata.modified())
+                        .unwrap_or(UNIX_EPOCH);
+
+                    Some((modified, path))
+                })
+                .collect();
+
+            if files.len() <= max_entries {
+                return files.len();
+            }
+
+            files.sort();
+
+            let mut left = files.len();
+
+            for (_, path) in &files[..files.len() - max_entries] {
+                match std::fs::remove_file(path) {
+                    Ok(()) => left -= 1,
+                    Err(err) => {
+                        log::warn!("Failed to evict cached page '{}': {err}", path.display());
+                    }
+                }
+            }
+
+            left
+        }
+    }
+
+    impl PageCache for DirectoryCache {
+        fn get(&self, key: &CacheKey) -> Option<WikipediaBody> {
+            let path = self.file_of(key);
+
+            let cached = Self::read(&path)?;
+
+            if self.is_expired(&cached) {
+                let _ = std::fs::remove_file(&path);
+
+                return None;
+            }
+
+            Some(cached.body)
+        }
+
+        fn insert(&self, key: CacheKey, body: WikipediaBody) {
+            let cached = CachedBody {
+                stored: now(),
+                body,
+            };
+
+            let path = self.file_of(&key);
+            let is_new = !path.exists();
+
+            let written = serde_json::to_vec(&cached)
+                .map_err(std::io::Error::other)
+                .and_then(|bytes| std::fs::write(path, bytes));
+
+            if let Err(err) = written {
+                log::warn!("Failed to cache page '{key}': {err}");
+
+                return;
+            }
+
+            let Some(max_entries) = self.max_entries else {
+                return;
+            };
+
+            // The directory is only listed when it might be over the limit, not on every insert
+            let mut entry_count = self.entry_count.lock().unwrap();
+
+            match entry_count.as_mut() {
+                Some(count) if *count < max_entries => *count += usize::from(is_new),
+                Some(_) if !is_new => {}
+                _ => *entry_count = Some(self.evict(max_entries)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn key(title: &str) -> CacheKey {
+        CacheKey::new(
+            WikiProject::Wikipedia,
+            WikiLanguage::from_code("en").unwrap(),
+            WikipediaUrlType::RawApi,
+            title,
+        )
+    }
+
+    fn body(title: &str) -> WikipediaBody {
+        WikipediaBody::Html(title.to_string())
+    }
+
+    #[test]
+    fn keys_are_normalized() {
+        assert_eq!(key("multekrem"), key(" Multekrem"));
+        assert_eq!(key("Belgian_waffle"), key("Belgian%20waffle"));
+        assert_ne!(key("Waffle"), key("Belgian waffle"));
+        assert_eq!(key("waffle").to_string(), "en/RawApi/Waffle");
+
+        let wiktionary = CacheKey::new(
+            WikiProject::Wiktionary,
+            WikiLanguage::English,
+            WikipediaUrlType::RawApi,
+            "waffle",
+        );
+
+        assert_ne!(wiktionary, key("waffle"));
+        assert_eq!(wiktionary.to_string(), "en.wiktionary/RawApi/waffle");
+
+        let limited = key("waffle").with_max_links(Some(10));
+
+        assert_ne!(limited, key("waffle"));
+        assert_eq!(limited.to_string(), "en/RawApi/Waffle?max_links=10");
+    }
+
+    #[test]
+    fn memory_cache_eviction() {
+        let cache = MemoryCache::new().with_max_entries(2);
+
+        cache.insert(key("A"), body("A"));
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        cache.insert(key("B"), body("B"));
+        std::thread::sleep(std::time::Duration::from_millis(2));
+        cache.insert(key("C"), body("C"));
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&key("A")).is_none());
+        assert_eq!(cache.get(&key("C")).unwrap().to_string(), "C");
+
+        let cache = MemoryCache::new().with_ttl(Duration::ZERO);
+
+        cache.insert(key("A"), body("A"));
+        std::thread::sleep(std::time::Duration::from_millis(2));
+
+        assert!(cache.get(&key("A")).is_none());
+        assert!(cache.is_empty());
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn directory_cache_eviction() {
+        let path = std::env::temp_dir().join(format!(
+            "wikipedia-graph-eviction-test-{}",
+            std::process::id()
+        ));
+
+        let cache = DirectoryCache::new(&path).unwrap().with_max_entries(2);
+
+        for title in ["A", "B", "A", "C"] {
+            cache.insert(key(title), body(title));
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+
+        // Writing "A" again didn't count as a new entry, so "B" is now the oldest
+        assert!(cache.get(&key("B")).is_none());
+        assert_eq!(cache.get(&key("A")).unwrap().to_string(), "A");
+        assert_eq!(std::fs::read_dir(&path).unwrap().count(), 2);
+
+        cache.insert(key("D"), body("D"));
+
+        assert!(cache.get(&key("A")).is_none());
+        assert_eq!(std::fs::read_dir(&path).unwrap().count(), 2);
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+}
@@ -1,18 +1,22 @@
-use super::WikipediaClientConfig;
+use super::batch::{self, BatchResult, MAX_BATCH_TITLES};
+use super::cache::{CacheKey, PageCache};
+use super::future::{ResponseFuture, join_all, oneshot, spawn_with_callback};
+use super::limiter::{Permit, RequestLimiter};
+use super::query::{self, QueryKind};
+use super::{BackoffPolicy, HttpTransport, WikipediaClientConfig, retry, timer};
 use crate::client::WikipediaClientCommon;
 use crate::page::{WikipediaBody, WikipediaLanguageInvalidError, WikipediaUrlType};
-use crate::{WikiLanguage, WikipediaPage};
+use crate::response::{self, QueryResponse, ResponseError};
+use crate::{PageSummary, WikiLanguage, WikiProject, WikipediaPage};
 use ehttp::{Headers, Request, Response};
 use http::StatusCode;
-use log::warn;
-use serde_json::Value;
+use serde::de::DeserializeOwned;
 use std::fmt::Display;
-#[allow(unused_imports)] // For wasm stuff
-use std::sync::{Arc, Mutex};
+use std::future::Future;
+use std::sync::Arc;
 use thiserror::Error;
 use url::Url;
-#[allow(unused_imports)] // For wasm stuff
-use web_time::{Duration, Instant};
+use web_time::Duration;
 
 /// The Errors that may occur with the HTTP client
 #[derive(Debug, Error, Clone)]
@@ -35,23 +39,146 @@ pub enum HttpError {
     /// The returned page has no body
     #[error("Failed to find page body")]
     NoPageBody,
-    /// The amount of redirects exceeded [crate::client::CLIENT_REDIRECTS]
+    /// The amount of redirects exceeded the client's limit, [crate::CLIENT_REDIRECTS] by default
     #[error("Too many redirects")]
     TooManyRedirects,
+    /// The redirects lead back to a page that was already visited
+    ///
+    /// Contains the titles of every page in the loop, in order
+    #[error("Redirect loop: {}", .0.join(" -> "))]
+    RedirectLoop(Vec<String>),
     /// The request returned an unknown response code
     #[error("Unknown response code: '{0}'")]
     Unknown(u16),
     /// The client failed to deserialise the response
     #[error("Failed to deserialise response: {0}")]
     DeserialisationError(String), // serde_json::Error doesn't implement Clone
+    /// The response was an API error, or is missing a field it should have
+    #[error("Invalid API response: {0}")]
+    InvalidResponse(ResponseError),
+    /// The API says the requested page doesn't exist, even though the request succeeded
+    #[error("The page doesn't exist")]
+    MissingTitle,
+    /// The API says the requested title isn't valid, contains the reason
+    #[error("Invalid title: {0}")]
+    InvalidTitle(String),
+    /// The API refused the request because the client sent too many
+    #[error("Rate limited by the API")]
+    RateLimited,
+    /// The API responded with an error without a variant of its own
+    #[error("API error '{code}': {info}")]
+    ApiError {
+        /// The machine readable error code, like `badvalue`
+        code: String,
+        /// The human readable description of the error
+        info: String,
+    },
+    /// The request still failed after retrying it as many times as the client allows
+    #[error("Request failed after {attempts} attempts: {source}")]
+    AttemptsExhausted {
+        /// The amount of attempts made, including the first
+        attempts: u32,
+        /// The error of the final attempt
+        source: Box<HttpError>,
+    },
+    /// A request failed, along with the URL it was sent to
+    ///
+    /// Use [HttpError::kind] to get the error without the URL
+    #[error("Request to '{url}' failed: {source}")]
+    Request {
+        /// The URL of the request
+        url: String,
+        /// The reason the request failed
+        source: Box<HttpError>,
+    },
+}
+
+impl HttpError {
+    /// Create the error for an `error` object sent by the API
+    pub(crate) fn from_api_error(code: String, info: String) -> Self {
+        match code.as_str() {
+            "missingtitle" => HttpError::MissingTitle,
+            "invalidtitle" => HttpError::InvalidTitle(info),
+            "ratelimited" => HttpError::RateLimited,
+            _ => HttpError::ApiError { code, info },
+        }
+    }
+
+    /// Attach the URL of the request that caused the error, keeping the first URL if there already is one
+    pub(crate) fn at(self, url: impl Display) -> Self {
+        match self {
+            HttpError::Request { .. } => self,
+            err => HttpError::Request {
+                url: url.to_string(),
+                source: Box::new(err),
+            },
+        }
+    }
+
+    /// Get the URL of the request that failed, if the error was caused by a request
+    pub fn url(&self) -> Option<&str> {
+        match self {
+            HttpError::Request { url, .. } => Some(url),
+            _ => None,
+        }
+    }
+
+    /// Get the error without the URL of the request that caused it
+    ///
+    /// # Example
+    /// ```
+    /// # use wikipedia_graph::HttpError;
+    /// let err = HttpError::Request {
+    ///     url: String::from("https://en.wikipedia.org/wiki/Nope"),
+    ///     source: Box::new(HttpError::PageNotFound),
+    /// };
+    ///
+    /// assert!(matches!(err.kind(), HttpError::PageNotFound));
+    /// ```
+    pub fn kind(&self) -> &HttpError {
+        match self {
+            HttpError::Request { source, .. } => source.kind(),
+            err => err,
+        }
+    }
+}
+
+impl From<ResponseError> for HttpError {
+    fn from(err: ResponseError) -> Self {
+        match err {
+            ResponseError::Deserialisation(err) => HttpError::DeserialisationError(err),
+            ResponseError::Api { code, info } => HttpError::from_api_error(code, info),
+            err => HttpError::InvalidResponse(err),
+        }
+    }
+}
+
+/// The body of a page loaded by the client, along with what was learned while loading it
+pub(crate) struct LoadedBody {
+    pub(crate) body: WikipediaBody,
+    /// The titles of every page that redirected to this page, in order
+    pub(crate) redirects: Vec<String>,
+    /// The `ETag` header of the response, if the server sent one
+    pub(crate) etag: Option<String>,
 }
 
 /// A client used for getting Wikipedia pages
 #[derive(Clone, Debug)]
 pub struct WikipediaClient {
     language: WikiLanguage,
+    project: WikiProject,
     headers: http::HeaderMap,
     url_type: WikipediaUrlType,
+    transport: Arc<dyn HttpTransport>,
+    timeout: Option<Duration>,
+    max_retries: u32,
+    backoff: BackoffPolicy,
+    limiter: Arc<RequestLimiter>,
+    max_redirects: usize,
+    max_links: Option<usize>,
+    max_backlinks: Option<usize>,
+    cache: Option<Arc<dyn PageCache>>,
+    offline: bool,
 }
 
 impl WikipediaClient {
@@ -97,16 +224,30 @@ impl WikipediaClient {
     ///     - A client side error
     ///     - Wikipedia is down
     pub fn get_api_base(&self, callback: impl Fn(Result<(), HttpError>) + 'static + Send) {
-        let callback =
-            move |result: Result<String, HttpError>| callback(result.map(|_: String| ()));
+        spawn_with_callback(self.get_api_base_async(), callback);
+    }
+
+    /// Get the contents of the page 'https://en.wikipedia.org/w/api.php', can be used as a network test
+    ///
+    /// Resolves upon request completion
+    ///
+    /// # Errors
+    ///
+    /// The method fails if the request fails
+    pub fn get_api_base_async(
+        &self,
+    ) -> impl Future<Output = Result<(), HttpError>> + Send + 'static {
+        let response = self.fetch(Request::get("https://en.wikipedia.org/w/api.php?origin=*"));
 
-        self.get_request(
-            Request::get(format!("https://en.wikipedia.org/w/api.php?origin=*")),
-            callback,
-        )
+        async move { response.await.map(|_: String| ()) }
     }
 
-    fn parse_status_code(code: StatusCode, response: Response) -> Result<Response, HttpError> {
+    /// Check the status of a response, 304 Not Modified is only a success for a conditional request
+    fn parse_status_code(
+        code: StatusCode,
+        response: Response,
+        conditional: bool,
+    ) -> Result<Response, HttpError> {
         // The api doesn't actually give redirect codes, hence this is pretty useless...
         //
         // if code.is_redirection() {
@@ -121,23 +262,27 @@ impl WikipediaClient {
             return Err(HttpError::PageNotFound);
         }
 
-        if code.is_success() {
+        // Conditional requests handle 304 themselves, for anything else it means there is no body
+        if code.is_success() || (conditional && code == StatusCode::NOT_MODIFIED) {
             return Ok(response);
         }
 
         Err(HttpError::Unknown(response.status))
     }
 
+    /// Send a single request, resolving with the raw response or a timeout
+    ///
+    /// The permit is held until the transport is done with the request, even if it timed out before that
     fn get_request(
         &self,
         request: Request,
-        callback: impl Fn(Result<String, HttpError>) + Send + 'static,
-    ) {
+        permit: Permit,
+    ) -> ResponseFuture<Result<Response, HttpError>> {
         log::info!("Loading page from url '{}'", &request.url);
 
         let mut request = request;
 
-        request.headers = Headers {
+        let mut headers = Headers {
             headers: self
                 .headers
                 .iter()
@@ -153,24 +298,194 @@ impl WikipediaClient {
                 .collect(),
         };
 
-        ehttp::fetch(request, move |response| {
-            let response_processed = response
-                .map_err(|err| HttpError::Backend(err))
-                .and_then(|response| match StatusCode::from_u16(response.status) {
-                    Ok(code) => WikipediaClient::parse_status_code(code, response),
-                    Err(_) => Err(HttpError::Unknown(response.status)),
-                })
-                .and_then(|response: Response| {
-                    response
-                        .text()
-                        .map(|text| text.to_string())
-                        .ok_or(HttpError::NoPageBody)
+        // Headers of the request itself, like `If-None-Match`, take precedence
+        for (name, value) in request.headers.headers {
+            headers.insert(name, value);
+        }
+
+        request.headers = headers;
+
+        let (completer, response) = oneshot();
+
+        if let Some(timeout) = self.timeout {
+            let completer = completer.clone();
+
+            timer::after(timeout, move || {
+                completer.complete(Err(HttpError::Timeout));
+            });
+        }
+
+        self.transport.fetch(
+            request,
+            Box::new(move |response| {
+                completer.complete(response.map_err(HttpError::Backend));
+
+                drop(permit);
+            }),
+        );
+
+        response
+    }
+
+    /// Send a request and resolve with the body of the response
+    ///
+    /// Timeouts and responses with a 429 or 5xx status are retried according to the client's configuration
+    pub(super) fn fetch(
+        &self,
+        request: Request,
+    ) -> impl Future<Output = Result<String, HttpError>> + Send + 'static {
+        self.fetch_with(request, |response| Self::response_text(&response))
+    }
+
+    /// Send a request to the API and resolve with the parsed response
+    ///
+    /// The API sends errors with a 200 status, these fail the request and `ratelimited` errors are retried
+    pub(super) fn fetch_parsed<T: DeserializeOwned + Send + 'static>(
+        &self,
+        request: Request,
+    ) -> impl Future<Output = Result<T, HttpError>> + Send + 'static {
+        self.fetch_with(request, |response| {
+            Ok(response::parse(&Self::response_text(&response)?)?)
+        })
+    }
+
+    /// Get the body of a response as text
+    pub(super) fn response_text(response: &Response) -> Result<String, HttpError> {
+        response
+            .text()
+            .map(ToString::to_string)
+            .ok_or(HttpError::NoPageBody)
+    }
+
+    /// Send a request and resolve with the successful response read by `parse`
+    ///
+    /// Timeouts, responses with a 429 or 5xx status and rate limit errors from `parse` are retried according to the client's configuration.
+    /// Reading the response is part of the request, so the body is only parsed once
+    pub(super) fn fetch_with<T: Send + 'static>(
+        &self,
+        request: Request,
+        parse: impl Fn(Response) -> Result<T, HttpError> + Send + 'static,
+    ) -> impl Future<Output = Result<T, HttpError>> + Send + 'static {
+        let client = self.clone();
+
+        async move {
+            if client.offline {
+                log::info!("Not loading '{}', the client is offline", request.url);
+
+                return Err(HttpError::PageNotFound.at(&request.url));
+            }
+
+            let conditional = [http::header::IF_NONE_MATCH, http::header::IF_MODIFIED_SINCE]
+                .iter()
+                .any(|header| request.headers.get(header.as_str()).is_some());
+
+            let mut attempts: u32 = 0;
+
+            loop {
+                attempts += 1;
+
+                let permit = client.limiter.acquire().await;
+
+                let response = client.get_request(request.clone(), permit).await;
+
+                let retry_after = match &response {
+                    Ok(response) if retry::is_retryable_status(response.status) => Some(
+                        response
+                            .headers
+                            .get(http::header::RETRY_AFTER.as_str())
+                            .and_then(retry::parse_retry_after),
+                    ),
+                    Err(HttpError::Timeout) => Some(None),
+                    _ => None,
+                };
+
+                let response = response.and_then(|response| {
+                    match StatusCode::from_u16(response.status) {
+                        Ok(code) => WikipediaClient::parse_status_code(code, response, conditional),
+                        Err(_) => Err(HttpError::Unknown(response.status)),
+                    }
+                    .and_then(&parse)
                 });
 
-            log::info!("Running callback... ");
+                let retry_after = retry_after
+                    .or_else(|| matches!(response, Err(HttpError::RateLimited)).then_some(None));
+
+                match (response, retry_after) {
+                    (Err(err), Some(retry_after)) if attempts <= client.max_retries => {
+                        let delay = retry_after.map_or_else(
+                            || client.backoff.delay(attempts - 1),
+                            |delay| delay.min(client.backoff.max_delay()),
+                        );
+
+                        log::warn!(
+                            "Request to '{}' failed ({err}), retrying in {delay:?}",
+                            request.url
+                        );
 
-            callback(response_processed);
-        });
+                        timer::sleep(delay).await;
+                    }
+                    // Errors that aren't worth retrying are returned as they are, even after a retry
+                    (Err(err), Some(_)) if attempts > 1 => {
+                        return Err(HttpError::AttemptsExhausted {
+                            attempts,
+                            source: Box::new(err),
+                        }
+                        .at(&request.url));
+                    }
+                    (response, _) => return response.map_err(|err| err.at(&request.url)),
+                }
+            }
+        }
+    }
+
+    /// Send a query API request and resolve with the parsed response
+    ///
+    /// The `continue` parameters of each response are followed until every result has been fetched or the limit is reached
+    pub(super) fn fetch_query(
+        &self,
+        url: Url,
+        kind: QueryKind,
+        limit: Option<usize>,
+    ) -> impl Future<Output = Result<QueryResponse, HttpError>> + Send + 'static {
+        let client = self.clone();
+
+        async move {
+            let fetch = |url: &Url| {
+                client.fetch_with(Request::get(url.as_str()), |response| {
+                    Ok(response::checked_value(&Self::response_text(&response)?)?)
+                })
+            };
+
+            let mut merged = fetch(&url).await?;
+
+            loop {
+                if let Some(limit) = limit
+                    && kind.truncate(&mut merged, limit)
+                {
+                    if let Some(merged) = merged.as_object_mut() {
+                        merged.remove("continue");
+                    }
+
+                    return response::from_value(merged)
+                        .map_err(|err| HttpError::from(err).at(&url));
+                }
+
+                let Some(params) = query::continue_params(&merged) else {
+                    return response::from_value(merged)
+                        .map_err(|err| HttpError::from(err).at(&url));
+                };
+
+                let mut continued_url = url.clone();
+
+                continued_url.query_pairs_mut().extend_pairs(params);
+
+                log::info!("Continuing query with '{continued_url}'");
+
+                let continued = fetch(&continued_url).await?;
+
+                kind.merge(&mut merged, continued);
+            }
+        }
     }
 
     /// Get the wikipedia page at the specified pathinfo
@@ -180,37 +495,455 @@ impl WikipediaClient {
     /// # Errors
     ///
     /// This method fails if the http request failed
-    pub fn get<'a, T: Display>(
-        &'a self,
+    pub fn get<T: Display>(
+        &self,
         pathinfo: T,
-        callback: impl Fn(Result<WikipediaBody, HttpError>) + Send + 'static + Clone,
+        callback: impl Fn(Result<WikipediaBody, HttpError>) + Send + 'static,
     ) -> Result<(), WikipediaLanguageInvalidError> {
-        let request = self.request_from_pathinfo(pathinfo, self.url_type)?;
+        let pathinfo = pathinfo.to_string();
 
-        let url_type = self.url_type.clone();
+        self.url_from_pathinfo(&pathinfo, self.url_type)?;
 
-        let client_clone: WikipediaClient = self.clone();
+        spawn_with_callback(self.get_async(pathinfo), callback);
 
-        self.get_request(request, move |response| {
-            let response = response.and_then(|body| {
-                WikipediaBody::from_url_type(url_type, body)
-                    .map_err(|err| HttpError::DeserialisationError(err.to_string()))
-            });
+        Ok(())
+    }
+
+    /// Get the wikipedia page at the specified pathinfo
+    ///
+    /// Redirects are followed until a page that isn't a redirect is found
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the http request failed, or the redirects loop or exceed the client's limit
+    pub fn get_async<T: Display>(
+        &self,
+        pathinfo: T,
+    ) -> impl Future<Output = Result<WikipediaBody, HttpError>> + Send + 'static {
+        let response = self.get_with_redirects_async(pathinfo, true);
+
+        async move { response.await.map(|loaded| loaded.body) }
+    }
+
+    /// Request the body of a single page without following redirects, along with its `ETag` header
+    pub(super) fn fetch_body(
+        &self,
+        request: Request,
+    ) -> impl Future<Output = Result<(WikipediaBody, Option<String>), HttpError>> + Send + 'static
+    {
+        let client = self.clone();
+
+        async move {
+            if client.url_type() == WikipediaUrlType::LinksApi {
+                let url = Url::parse(&request.url)?;
+
+                let body = client
+                    .fetch_query(url.clone(), QueryKind::Prop("links"), client.max_links)
+                    .await?;
+
+                if let Some(err) = body.first_page().ok().and_then(batch::page_error) {
+                    return Err(err.at(url));
+                }
+
+                return Ok((WikipediaBody::Links(body), None));
+            }
+
+            let url_type = client.url_type();
+
+            client
+                .fetch_with(request, move |response| {
+                    let etag = response
+                        .headers
+                        .get(http::header::ETAG.as_str())
+                        .map(ToString::to_string);
+
+                    let body =
+                        WikipediaBody::from_url_type(url_type, Self::response_text(&response)?)?;
 
-            if let Ok(page) = &response
-                && let Some(redirect_page) = page.redirects_to()
-            {
-                if let Err(e) = client_clone.get(redirect_page.pathinfo(), callback.clone()) {
-                    warn!("Redirect failed: {e}");
+                    Ok((body, etag))
+                })
+                .await
+        }
+    }
+
+    /// Get the wikipedia page at the specified pathinfo, along with the titles of every page it was redirected through
+    ///
+    /// The cache is skipped unless `use_cache` is set, but is always updated
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the http request failed, or the redirects loop or exceed the client's limit
+    pub(crate) fn get_with_redirects_async<T: Display>(
+        &self,
+        pathinfo: T,
+        use_cache: bool,
+    ) -> impl Future<Output = Result<LoadedBody, HttpError>> + Send + 'static {
+        let client = self.clone();
+
+        let mut page = WikipediaPage::from_title(pathinfo.to_string());
+
+        async move {
+            let mut redirects: Vec<String> = Vec::new();
+
+            loop {
+                let request = client.request_from_pathinfo(page.pathinfo(), client.url_type())?;
+
+                let url = request.url.clone();
+
+                let cached = match use_cache {
+                    true => client.cached(client.url_type(), page.pathinfo()),
+                    false => None,
+                };
+
+                let (body, etag) = match cached {
+                    Some(body) => (body, None),
+                    None => {
+                        let (body, etag) = client.fetch_body(request).await?;
+
+                        client.store(client.url_type(), page.pathinfo(), &body);
+
+                        (body, etag)
+                    }
+                };
+
+                let Some(redirect_page) = body.redirects_to() else {
+                    return Ok(LoadedBody {
+                        body,
+                        redirects,
+                        etag,
+                    });
+                };
+
+                log::info!(
+                    "Redirecting from '{}' to '{}'",
+                    page.pathinfo(),
+                    redirect_page.pathinfo()
+                );
+
+                redirects.push(page.title());
+
+                if redirects.contains(&redirect_page.title()) {
+                    return Err(HttpError::RedirectLoop(redirects).at(url));
                 }
-            } else {
-                callback(response)
+
+                if redirects.len() > client.max_redirects {
+                    return Err(HttpError::TooManyRedirects.at(url));
+                }
+
+                page = redirect_page;
             }
-        });
+        }
+    }
+
+    /// Get the links of several wikipedia pages, sending one request per [MAX_BATCH_TITLES] titles
+    ///
+    /// Executes the given callback once every batch has completed, with a result for each title in the order they were given
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the client's language has no valid URL, failures of single pages are reported per title
+    pub fn get_many<T: Display>(
+        &self,
+        titles: impl IntoIterator<Item = T>,
+        callback: impl Fn(Vec<(String, Result<WikipediaBody, HttpError>)>) + Send + 'static,
+    ) -> Result<(), WikipediaLanguageInvalidError> {
+        WikipediaUrlType::LinksApi.base_url(self.project, self.language)?;
+
+        spawn_with_callback(self.get_many_async(titles), callback);
 
         Ok(())
     }
 
+    /// Get the links of several wikipedia pages, sending one request per [MAX_BATCH_TITLES] titles
+    ///
+    /// Resolves once every batch has completed, with a result for each title in the order they were given
+    ///
+    /// Redirects are resolved by the API, so the bodies may belong to a different title than the one requested
+    pub fn get_many_async<T: Display>(
+        &self,
+        titles: impl IntoIterator<Item = T>,
+    ) -> impl Future<Output = Vec<(String, Result<WikipediaBody, HttpError>)>> + Send + 'static
+    {
+        let response = self.get_many_with_redirects_async(titles);
+
+        async move {
+            response
+                .await
+                .into_iter()
+                .map(|(title, result)| (title, result.map(|(body, _)| body)))
+                .collect()
+        }
+    }
+
+    /// Get the links of several wikipedia pages, along with the titles each page was redirected from
+    pub(crate) fn get_many_with_redirects_async<T: Display>(
+        &self,
+        titles: impl IntoIterator<Item = T>,
+    ) -> impl Future<Output = Vec<(String, BatchResult)>> + Send + 'static {
+        let titles: Vec<String> = titles.into_iter().map(|title| title.to_string()).collect();
+
+        let cached: Vec<Option<WikipediaBody>> = titles
+            .iter()
+            .map(|title| self.cached(WikipediaUrlType::LinksApi, title))
+            .collect();
+
+        let missing: Vec<String> = titles
+            .iter()
+            .zip(&cached)
+            .filter(|(_, cached)| cached.is_none())
+            .map(|(title, _)| title.clone())
+            .collect();
+
+        let batches = missing
+            .chunks(MAX_BATCH_TITLES)
+            .map(|titles| {
+                let titles = titles.to_vec();
+
+                let response = WikipediaUrlType::batch_links_url(
+                    self.project,
+                    self.language,
+                    &titles,
+                )
+                .map(|url| {
+                    (
+                        self.fetch_query(url.clone(), QueryKind::Prop("links"), self.max_links),
+                        url,
+                    )
+                });
+
+                async move {
+                    match response {
+                        Ok((response, url)) => match response.await {
+                            Ok(body) => batch::split_batch(&titles, &body)
+                                .into_iter()
+                                .map(|(title, result)| (title, result.map_err(|err| err.at(&url))))
+                                .collect(),
+                            Err(err) => batch::all_failed(&titles, &err),
+                        },
+                        Err(err) => batch::all_failed(&titles, &err.into()),
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let client = self.clone();
+
+        async move {
+            let mut fetched = join_all(batches).await.into_iter().flatten();
+
+            titles
+                .into_iter()
+                .zip(cached)
+                .map(|(title, cached)| match cached {
+                    Some(body) => {
+                        let redirects = batch::redirected_from(&body);
+
+                        (title, Ok((body, redirects)))
+                    }
+                    None => {
+                        let (title, result) = fetched
+                            .next()
+                            .unwrap_or((title, Err(HttpError::NoPageBody)));
+
+                        if let Ok((body, _)) = &result {
+                            client.store(WikipediaUrlType::LinksApi, &title, body);
+                        }
+
+                        (title, result)
+                    }
+                })
+                .collect()
+        }
+    }
+
+    /// Get the pages that link to the page at the specified pathinfo, also known as "what links here"
+    ///
+    /// Executes the given callback upon request completion
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the client's language has no valid URL
+    pub fn get_backlinks<T: Display>(
+        &self,
+        pathinfo: T,
+        callback: impl Fn(Result<Vec<WikipediaPage>, HttpError>) + Send + 'static,
+    ) -> Result<(), WikipediaLanguageInvalidError> {
+        WikipediaUrlType::LinksApi.base_url(self.project, self.language)?;
+
+        spawn_with_callback(self.get_backlinks_async(pathinfo), callback);
+
+        Ok(())
+    }
+
+    /// Get the pages that link to the page at the specified pathinfo, also known as "what links here"
+    ///
+    /// Only articles are included, and redirects to the page are skipped. At most [WikipediaClientConfig::max_backlinks] pages are fetched
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the request failed
+    pub fn get_backlinks_async<T: Display>(
+        &self,
+        pathinfo: T,
+    ) -> impl Future<Output = Result<Vec<WikipediaPage>, HttpError>> + Send + 'static {
+        let response =
+            WikipediaUrlType::backlinks_url(self.project, self.language, &pathinfo.to_string())
+                .map(|url| {
+                    (
+                        self.fetch_query(
+                            url.clone(),
+                            QueryKind::List("backlinks"),
+                            self.max_backlinks,
+                        ),
+                        url,
+                    )
+                });
+
+        let (project, language) = (self.project, self.language);
+
+        async move {
+            let (response, url) = response?;
+
+            let backlinks = |body: QueryResponse| -> Result<Vec<WikipediaPage>, HttpError> {
+                Ok(body
+                    .query()?
+                    .backlinks()?
+                    .iter()
+                    .map(|page| {
+                        WikipediaPage::from_title(page.title.as_str())
+                            .with_namespace_id(page.ns)
+                            .with_project(project)
+                            .with_language(language)
+                    })
+                    .collect())
+            };
+
+            backlinks(response.await?).map_err(|err| err.at(url))
+        }
+    }
+
+    /// Get the pages that are the page at the specified pathinfo on the wikis of other languages
+    ///
+    /// Executes the given callback upon request completion
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the client's language has no valid URL
+    pub fn get_langlinks<T: Display>(
+        &self,
+        pathinfo: T,
+        callback: impl Fn(Result<Vec<WikipediaPage>, HttpError>) + Send + 'static,
+    ) -> Result<(), WikipediaLanguageInvalidError> {
+        WikipediaUrlType::LinksApi.base_url(self.project, self.language)?;
+
+        spawn_with_callback(self.get_langlinks_async(pathinfo), callback);
+
+        Ok(())
+    }
+
+    /// Get the pages that are the page at the specified pathinfo on the wikis of other languages
+    ///
+    /// Every page is in its own language on the client's project. Links to wikis that aren't a [WikiLanguage] with an edition of the project are skipped.
+    /// Pages with more interlanguage links than fit in one response are continued until every link is fetched
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the request failed or the page doesn't exist
+    pub fn get_langlinks_async<T: Display>(
+        &self,
+        pathinfo: T,
+    ) -> impl Future<Output = Result<Vec<WikipediaPage>, HttpError>> + Send + 'static {
+        let request =
+            WikipediaUrlType::langlinks_url(self.project, self.language, &pathinfo.to_string())
+                .map(|url| {
+                    (
+                        self.fetch_query(url.clone(), QueryKind::Prop("langlinks"), None),
+                        url,
+                    )
+                });
+
+        let project = self.project;
+
+        async move {
+            let (request, url) = request?;
+
+            let langlinks = |body: QueryResponse| -> Result<Vec<WikipediaPage>, HttpError> {
+                let page = body.first_page()?;
+
+                if let Some(err) = batch::page_error(page) {
+                    return Err(err);
+                }
+
+                Ok(page
+                    .langlinks
+                    .iter()
+                    .filter_map(|langlink| {
+                        let language = WikiLanguage::from_code(&langlink.lang)
+                            .filter(|language| project.language_code(language).is_some())?;
+
+                        Some(
+                            WikipediaPage::from_title(langlink.title.as_str())
+                                .with_project(project)
+                                .with_language(language),
+                        )
+                    })
+                    .collect())
+            };
+
+            langlinks(request.await?).map_err(|err| err.at(url))
+        }
+    }
+
+    /// Get the plain text introduction, description and thumbnail of the page at the specified pathinfo
+    ///
+    /// Executes the given callback upon request completion
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the client's language has no valid URL
+    pub fn get_summary<T: Display>(
+        &self,
+        pathinfo: T,
+        callback: impl Fn(Result<PageSummary, HttpError>) + Send + 'static,
+    ) -> Result<(), WikipediaLanguageInvalidError> {
+        WikipediaUrlType::LinksApi.base_url(self.project, self.language)?;
+
+        spawn_with_callback(self.get_summary_async(pathinfo), callback);
+
+        Ok(())
+    }
+
+    /// Get the plain text introduction, description and thumbnail of the page at the specified pathinfo
+    ///
+    /// Redirects are resolved by the API
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the request failed or the page doesn't exist
+    pub fn get_summary_async<T: Display>(
+        &self,
+        pathinfo: T,
+    ) -> impl Future<Output = Result<PageSummary, HttpError>> + Send + 'static {
+        let request =
+            WikipediaUrlType::summary_url(self.project, self.language, &pathinfo.to_string())
+                .map(|url| (self.fetch_parsed(Request::get(url.as_str())), url));
+
+        async move {
+            let (request, url) = request?;
+
+            let summary = |body: QueryResponse| -> Result<PageSummary, HttpError> {
+                let page = body.first_page()?;
+
+                match batch::page_error(page) {
+                    Some(err) => Err(err),
+                    None => Ok(PageSummary::from_query_page(page)),
+                }
+            };
+
+            summary(request.await?).map_err(|err| err.at(url))
+        }
+    }
+
     /// returns the title of a random page using the Wikimedia API
     ///
     /// Executes the given callback upon request completion
@@ -222,43 +955,135 @@ impl WikipediaClient {
         &self,
         callback: impl Fn(Result<WikipediaPage, HttpError>) + Send + 'static,
     ) -> Result<(), WikipediaLanguageInvalidError> {
-        let mut base_url = WikipediaUrlType::LinksApi.base_url(self.language)?;
-
-        base_url.set_query(Some(
-            "action=query&format=json&list=random&rnnamespace=0&rnlimit=1&origin=*",
-        ));
-
-        let request = Request::get(base_url);
-
-        let callback = move |response: Result<String, HttpError>| {
-            callback(response.and_then(|body| {
-                serde_json::from_str::<Value>(body.as_str())
-                    .map_err(|err| HttpError::DeserialisationError(err.to_string()))?
-                    .get("query")
-                    .and_then(|val| val.get("random"))
-                    .and_then(|val| val.as_array())
-                    .and_then(|data| {
-                        data.get(0)
-                            .and_then(|data| data.get("title"))
-                            .and_then(|title| title.as_str())
-                            .map(|title| title.to_string())
+        WikipediaUrlType::LinksApi.base_url(self.project, self.language)?;
+
+        spawn_with_callback(self.random_page_async(), callback);
+
+        Ok(())
+    }
+
+    /// returns the title of a random page using the Wikimedia API
+    ///
+    /// Resolves upon request completion
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the request failed
+    pub fn random_page_async(
+        &self,
+    ) -> impl Future<Output = Result<WikipediaPage, HttpError>> + Send + 'static {
+        let request = WikipediaUrlType::LinksApi
+            .base_url(self.project, self.language)
+            .map(|mut base_url| {
+                base_url.set_query(Some(
+                    "action=query&format=json&list=random&rnnamespace=0&rnlimit=1&origin=*",
+                ));
+
+                (self.fetch_parsed(Request::get(base_url.as_str())), base_url)
+            });
+
+        let (project, language) = (self.project, self.language);
+
+        async move {
+            let (request, url) = request?;
+
+            let random = |body: QueryResponse| -> Result<WikipediaPage, HttpError> {
+                body.query()?
+                    .random()?
+                    .first()
+                    .map(|page| {
+                        WikipediaPage::from_title(page.title.as_str())
+                            .with_project(project)
+                            .with_language(language)
                     })
                     .ok_or(HttpError::NoPageBody)
-                    .map(WikipediaPage::from_title)
-            }));
+            };
+
+            random(request.await?).map_err(|err| err.at(url))
+        }
+    }
+
+    /// Get the key a body is cached under, links bodies are keyed by the client's link limit as well
+    fn cache_key(&self, url_type: WikipediaUrlType, title: &str) -> CacheKey {
+        let max_links = match url_type {
+            WikipediaUrlType::LinksApi => self.max_links,
+            _ => None,
         };
 
-        self.get_request(request, callback);
+        CacheKey::new(self.project, self.language, url_type, title).with_max_links(max_links)
+    }
 
-        Ok(())
+    /// Get a body from the client's cache, if it has one
+    pub(super) fn cached(&self, url_type: WikipediaUrlType, title: &str) -> Option<WikipediaBody> {
+        let body = self.cache.as_ref()?.get(&self.cache_key(url_type, title));
+
+        if body.is_some() {
+            log::info!("Loaded '{title}' from the cache");
+        }
+
+        body
+    }
+
+    /// Store a body in the client's cache, if it has one
+    pub(super) fn store(&self, url_type: WikipediaUrlType, title: &str, body: &WikipediaBody) {
+        if let Some(cache) = &self.cache {
+            cache.insert(self.cache_key(url_type, title), body.clone());
+        }
+    }
+
+    /// Get the kind of URL the client requests pages from
+    pub fn url_type(&self) -> WikipediaUrlType {
+        self.url_type
+    }
+
+    /// Get the language of the wiki the client requests pages from
+    pub fn language(&self) -> WikiLanguage {
+        self.language
+    }
+
+    /// Get the project of the wiki the client requests pages from
+    pub fn project(&self) -> WikiProject {
+        self.project
+    }
+
+    /// Get a client that requests pages in another language
+    ///
+    /// The new client shares the transport, rate limit and cache of this client
+    pub fn for_language(&self, language: WikiLanguage) -> Self {
+        Self {
+            language,
+            ..self.clone()
+        }
+    }
+
+    /// Get a client that requests pages from another wiki, like the German Wiktionary
+    ///
+    /// The new client shares the transport, rate limit and cache of this client
+    pub fn for_wiki(&self, project: WikiProject, language: WikiLanguage) -> Self {
+        Self {
+            project,
+            language,
+            ..self.clone()
+        }
     }
 
     /// Create a [WikipediaClient] from a [WikipediaClientConfig]
     pub fn from_config(config: WikipediaClientConfig) -> Self {
         WikipediaClient {
             language: config.language,
+            project: config.project,
             headers: config.headers,
             url_type: config.url_type,
+            transport: config.transport,
+            timeout: config.timeout,
+            max_retries: config.max_retries,
+            backoff: config.backoff,
+            limiter: Arc::new(RequestLimiter::new(config.rate_limit, config.max_in_flight)),
+            max_redirects: config.max_redirects,
+            max_links: config.max_links,
+            max_backlinks: config.max_backlinks,
+            cache: config.cache,
+            offline: config.offline,
         }
     }
 }
@@ -267,6 +1092,10 @@ impl WikipediaClientCommon for WikipediaClient {
     fn language(&self) -> WikiLanguage {
         self.language
     }
+
+    fn project(&self) -> WikiProject {
+        self.project
+    }
 }
 
 impl Default for WikipediaClient {
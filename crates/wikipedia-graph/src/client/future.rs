@@ -0,0 +1,208 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::task::{Context, Poll, Wake, Waker};
+
+/// Lock a mutex, ignoring poisoning since none of the guarded state can be left half-updated
+pub(crate) fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+struct OneshotState<T> {
+    value: Option<T>,
+    waker: Option<Waker>,
+    completed: bool,
+}
+
+/// A future that resolves once its [Completer] is called
+///
+/// This is how callbacks from the transport are turned into futures
+pub(crate) struct ResponseFuture<T> {
+    state: Arc<Mutex<OneshotState<T>>>,
+}
+
+/// The sending half of a [ResponseFuture]
+///
+/// Only the first value passed to a completer (or any of its clones) is kept
+pub(crate) struct Completer<T> {
+    state: Arc<Mutex<OneshotState<T>>>,
+}
+
+impl<T> Clone for Completer<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: Arc::clone(&self.state),
+        }
+    }
+}
+
+/// Create a linked [Completer] and [ResponseFuture]
+pub(crate) fn oneshot<T>() -> (Completer<T>, ResponseFuture<T>) {
+    let state = Arc::new(Mutex::new(OneshotState {
+        value: None,
+        waker: None,
+        completed: false,
+    }));
+
+    (
+        Completer {
+            state: Arc::clone(&state),
+        },
+        ResponseFuture { state },
+    )
+}
+
+impl<T> Completer<T> {
+    /// Resolve the linked future, returns false if it was already resolved
+    pub(crate) fn complete(&self, value: T) -> bool {
+        let waker = {
+            let mut state = lock(&self.state);
+
+            if state.completed {
+                return false;
+            }
+
+            state.completed = true;
+            state.value = Some(value);
+            state.waker.take()
+        };
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+
+        true
+    }
+}
+
+impl<T> Future for ResponseFuture<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = lock(&self.state);
+
+        match state.value.take() {
+            Some(value) => Poll::Ready(value),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+type BoxedFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
+type BoxedCallback<T> = Box<dyn FnOnce(T) + Send>;
+
+/// A future that polls itself whenever it is woken, then hands its output to a callback
+///
+/// This lets the callback API run on top of the async API without needing an executor
+struct CallbackTask<T> {
+    future: Mutex<Option<(BoxedFuture<T>, BoxedCallback<T>)>>,
+    notified: AtomicBool,
+}
+
+impl<T: 'static> CallbackTask<T> {
+    fn run(self: Arc<Self>) {
+        self.notified.store(true, Ordering::SeqCst);
+
+        // Whoever holds the lock is responsible for polling again after a wake
+        while let Ok(mut slot) = self.future.try_lock() {
+            if !self.notified.swap(false, Ordering::SeqCst) {
+                return;
+            }
+
+            let Some((future, _)) = slot.as_mut() else {
+                return;
+            };
+
+            let waker = Waker::from(Arc::clone(&self));
+
+            if let Poll::Ready(output) = future.as_mut().poll(&mut Context::from_waker(&waker)) {
+                let callback = slot.take().map(|(_, callback)| callback);
+
+                drop(slot);
+
+                if let Some(callback) = callback {
+                    callback(output);
+                }
+
+                return;
+            }
+
+            drop(slot);
+
+            if !self.notified.load(Ordering::SeqCst) {
+                return;
+            }
+        }
+    }
+}
+
+impl<T: 'static> Wake for CallbackTask<T> {
+    fn wake(self: Arc<Self>) {
+        self.run();
+    }
+}
+
+/// Drive a future to completion on whichever thread wakes it, then run the callback with its output
+pub(crate) fn spawn_with_callback<T: 'static>(
+    future: impl Future<Output = T> + Send + 'static,
+    callback: impl FnOnce(T) + Send + 'static,
+) {
+    Arc::new(CallbackTask {
+        future: Mutex::new(Some((Box::pin(future), Box::new(callback)))),
+        notified: AtomicBool::new(false),
+    })
+    .run();
+}
+
+/// A future being joined, along with its output once it has resolved
+type JoinSlot<F> = (Pin<Box<F>>, Option<<F as Future>::Output>);
+
+/// A future that resolves once every future in it has resolved, keeping their order
+pub(crate) struct JoinAll<F: Future> {
+    futures: Vec<JoinSlot<F>>,
+}
+
+/// Run every future concurrently, resolving with all of their outputs in order
+pub(crate) fn join_all<F: Future>(futures: impl IntoIterator<Item = F>) -> JoinAll<F> {
+    JoinAll {
+        futures: futures
+            .into_iter()
+            .map(|future| (Box::pin(future), None))
+            .collect(),
+    }
+}
+
+// The futures are boxed and the outputs are never pinned, so moving the struct is fine
+impl<F: Future> Unpin for JoinAll<F> {}
+
+impl<F: Future> Future for JoinAll<F> {
+    type Output = Vec<F::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        for (future, output) in &mut this.futures {
+            if output.is_none()
+                && let Poll::Ready(value) = future.as_mut().poll(cx)
+            {
+                *output = Some(value);
+            }
+        }
+
+        if this.futures.iter().all(|(_, output)| output.is_some()) {
+            Poll::Ready(
+                this.futures
+                    .drain(..)
+                    .filter_map(|(_, output)| output)
+                    .collect(),
+            )
+        } else {
+            Poll::Pending
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use super::future::{Completer, ResponseFuture, lock, oneshot};
+use super::timer;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use web_time::{Duration, Instant};
+
+/// A token bucket limiting how many requests a client may start per second
+///
+/// *This struct requires the `client` feature*
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RateLimit {
+    per_second: f64,
+    burst: u32,
+}
+
+impl RateLimit {
+    /// Allow `per_second` requests per second on average, with up to `burst` requests at once
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `per_second` isn't positive or `burst` is zero
+    pub fn new(per_second: f64, burst: u32) -> Self {
+        assert!(per_second > 0.0, "Rate limit must be positive");
+        assert!(burst > 0, "Rate limit burst must be at least one");
+
+        Self { per_second, burst }
+    }
+
+    /// The average amount of requests allowed per second
+    pub fn per_second(&self) -> f64 {
+        self.per_second
+    }
+
+    /// The amount of requests allowed at once
+    pub fn burst(&self) -> u32 {
+        self.burst
+    }
+}
+
+struct LimiterState {
+    tokens: f64,
+    last_refill: Instant,
+    in_flight: usize,
+    queue: VecDeque<Completer<Permit>>,
+    timer_scheduled: bool,
+}
+
+/// Queues requests until both the rate limit and the concurrency cap allow them to start
+///
+/// Requests are started in the order they were queued
+#[derive(Debug)]
+pub(crate) struct RequestLimiter {
+    rate_limit: Option<RateLimit>,
+    max_in_flight: Option<usize>,
+    state: Mutex<LimiterState>,
+}
+
+impl std::fmt::Debug for LimiterState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LimiterState")
+            .field("tokens", &self.tokens)
+            .field("in_flight", &self.in_flight)
+            .field("queued", &self.queue.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Permission to have a single request in flight, released when dropped
+pub(crate) struct Permit {
+    limiter: Arc<RequestLimiter>,
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        lock(&self.limiter.state).in_flight -= 1;
+
+        self.limiter.dispatch();
+    }
+}
+
+impl RequestLimiter {
+    pub(crate) fn new(rate_limit: Option<RateLimit>, max_in_flight: Option<usize>) -> Self {
+        Self {
+            rate_limit,
+            max_in_flight: max_in_flight.map(|max| max.max(1)),
+            state: Mutex::new(LimiterState {
+                tokens: rate_limit.map_or(0.0, |rate_limit| f64::from(rate_limit.burst)),
+                last_refill: Instant::now(),
+                in_flight: 0,
+                queue: VecDeque::new(),
+                timer_scheduled: false,
+            }),
+        }
+    }
+
+    /// Wait in the queue until a request may be sent
+    pub(crate) fn acquire(self: &Arc<Self>) -> ResponseFuture<Permit> {
+        let (completer, permit) = oneshot();
+
+        lock(&self.state).queue.push_back(completer);
+
+        self.dispatch();
+
+        permit
+    }
+
+    /// Hand out permits to the front of the queue for as long as the limits allow
+    fn dispatch(self: &Arc<Self>) {
+        let mut ready = Vec::new();
+
+        let wait = {
+            let mut state = lock(&self.state);
+
+            if let Some(rate_limit) = self.rate_limit {
+                let now = Instant::now();
+
+                state.tokens = (state.tokens
+                    + now.duration_since(state.last_refill).as_secs_f64() * rate_limit.per_second)
+                    .min(f64::from(rate_limit.burst));
+                state.last_refill = now;
+            }
+
+            let mut wait = None;
+
+            while !state.queue.is_empty()
+                && self
+                    .max_in_flight
+                    .is_none_or(|max_in_flight| state.in_flight < max_in_flight)
+            {
+                if let Some(rate_limit) = self.rate_limit {
+                    if state.tokens < 1.0 {
+                        wait = Some(Duration::from_secs_f64(
+                            (1.0 - state.tokens) / rate_limit.per_second,
+                        ));
+                        break;
+                    }
+
+                    state.tokens -= 1.0;
+                }
+
+                state.in_flight += 1;
+
+                if let Some(completer) = state.queue.pop_front() {
+                    ready.push(completer);
+                }
+            }
+
+            match wait {
+                Some(wait) if !state.timer_scheduled => {
+                    state.timer_scheduled = true;
+                    Some(wait)
+                }
+                _ => None,
+            }
+        };
+
+        if let Some(wait) = wait {
+            let limiter = Arc::clone(self);
+
+            timer::after(wait, move || {
+                lock(&limiter.state).timer_scheduled = false;
+
+                limiter.dispatch();
+            });
+        }
+
+        // Completing wakes the waiting requests, which may need the lock again
+        for completer in ready {
+            completer.complete(Permit {
+                limiter: Arc::clone(self),
+            });
+        }
+    }
+}
@@ -1,14 +1,42 @@
+mod batch;
+mod cache;
+#[allow(clippy::module_inception)]
 mod client;
+mod future;
+mod limiter;
+mod query;
+mod refresh;
+mod resolve;
+mod retry;
+mod timer;
+mod transport;
+pub use batch::MAX_BATCH_TITLES;
+#[cfg(not(target_arch = "wasm32"))]
+pub use cache::DirectoryCache;
+pub use cache::{CacheKey, MemoryCache, PageCache};
 pub use client::*;
+pub(crate) use future::{join_all, spawn_with_callback};
+pub use limiter::RateLimit;
+pub use refresh::RefreshReport;
+pub use resolve::ResolvedTitles;
+pub use retry::BackoffPolicy;
+pub use transport::*;
+
+#[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+mod blocking;
+#[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+pub use blocking::BlockingWikipediaClient;
 
 use crate::{
     page::{WikipediaLanguageInvalidError, WikipediaUrlType},
+    project::WikiProject,
     wikimedia_languages::WikiLanguage,
 };
 use http::{HeaderMap, HeaderName, HeaderValue};
-use std::{collections::HashMap, str::FromStr};
+use std::{collections::HashMap, str::FromStr, sync::Arc};
 use thiserror::Error;
 use url::Url;
+use web_time::Duration;
 
 /// The configuration for a WikipediaClient
 ///
@@ -17,11 +45,26 @@ pub struct WikipediaClientConfig {
     // Only non-default headers
     headers: HeaderMap<HeaderValue>,
     language: WikiLanguage,
+    project: WikiProject,
     url_type: WikipediaUrlType,
+    transport: Arc<dyn HttpTransport>,
+    timeout: Option<Duration>,
+    max_retries: u32,
+    backoff: BackoffPolicy,
+    rate_limit: Option<RateLimit>,
+    max_in_flight: Option<usize>,
+    max_redirects: usize,
+    max_links: Option<usize>,
+    max_backlinks: Option<usize>,
+    cache: Option<Arc<dyn PageCache>>,
+    offline: bool,
 }
 
+/// The default amount of redirects a client follows before failing with [HttpError::TooManyRedirects]
+pub const CLIENT_REDIRECTS: usize = 10;
+
 /// The default user agent
-const USER_AGENT: &'static str = concat!(
+const USER_AGENT: &str = concat!(
     std::env!("CARGO_PKG_NAME"),
     "/",
     std::env!("CARGO_PKG_VERSION")
@@ -75,6 +118,24 @@ impl WikipediaClientConfig {
         Self { language, ..self }
     }
 
+    /// Sets the wiki project pages are requested from
+    ///
+    /// For example, with [WikiProject::Wiktionary] the 'waffle' page becomes the URL 'https://{language code}.wiktionary.org/wiki/waffle'
+    ///
+    /// The default value is [WikiProject::Wikipedia]
+    pub fn project(self, project: WikiProject) -> Self {
+        Self { project, ..self }
+    }
+
+    /// Sets the kind of URL pages are requested from
+    ///
+    /// [WikipediaUrlType::LinksApi] uses the structured links API, while [WikipediaUrlType::RawApi] finds links in the wikitext and [WikipediaUrlType::Basic] finds them in the HTML article
+    ///
+    /// The default value is [WikipediaUrlType::RawApi]
+    pub fn url_type(self, url_type: WikipediaUrlType) -> Self {
+        Self { url_type, ..self }
+    }
+
     /// Adds a header to the request
     ///
     /// This is helpful for CORS authentication and probably a few other things
@@ -95,6 +156,123 @@ impl WikipediaClientConfig {
         Ok(self)
     }
 
+    /// Sets the transport used to send requests
+    ///
+    /// This can be used to swap the HTTP backend, add middleware or serve requests from a [FixtureTransport]
+    ///
+    /// The default value is [EhttpTransport]
+    pub fn transport(self, transport: impl HttpTransport + 'static) -> Self {
+        Self {
+            transport: Arc::new(transport),
+            ..self
+        }
+    }
+
+    /// Sets how long a single request may take before it fails with [HttpError::Timeout]
+    ///
+    /// Passing [None] lets requests take as long as the transport allows.
+    /// The transport isn't told about the timeout, so a request that timed out may still finish in the background, its response is ignored
+    ///
+    /// The default value is 30 seconds
+    pub fn timeout(self, timeout: Option<Duration>) -> Self {
+        Self { timeout, ..self }
+    }
+
+    /// Sets how many times a request is retried after it times out or returns a 429 or 5xx status
+    ///
+    /// Once every retry fails, the error is wrapped in [HttpError::AttemptsExhausted].
+    /// Errors that aren't worth retrying, like a 404, are returned as they are
+    ///
+    /// The default value is 3
+    pub fn max_retries(self, max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            ..self
+        }
+    }
+
+    /// Sets how long to wait between retries when the server doesn't send a `Retry-After` header
+    ///
+    /// The default value is an exponential backoff starting at 500ms
+    pub fn backoff(self, backoff: BackoffPolicy) -> Self {
+        Self { backoff, ..self }
+    }
+
+    /// Sets how many requests the client may start per second, [None] removes the limit
+    ///
+    /// Requests over the limit are queued and sent in order. Clones of a client share the same limit
+    ///
+    /// The default value is 20 requests per second, with bursts of up to 20 requests
+    pub fn rate_limit(self, rate_limit: Option<RateLimit>) -> Self {
+        Self { rate_limit, ..self }
+    }
+
+    /// Sets how many requests the client may have in flight at once, [None] removes the limit
+    ///
+    /// Requests over the limit are queued and sent in order. Clones of a client share the same limit
+    ///
+    /// The default value is 10
+    pub fn max_in_flight(self, max_in_flight: Option<usize>) -> Self {
+        Self {
+            max_in_flight,
+            ..self
+        }
+    }
+
+    /// Sets how many `#REDIRECT` pages the client follows before failing with [HttpError::TooManyRedirects]
+    ///
+    /// Redirects that loop back to an earlier page always fail with [HttpError::RedirectLoop]
+    ///
+    /// The default value is [CLIENT_REDIRECTS]
+    pub fn max_redirects(self, max_redirects: usize) -> Self {
+        Self {
+            max_redirects,
+            ..self
+        }
+    }
+
+    /// Sets the most links fetched for a single page from the links API, [None] fetches every link
+    ///
+    /// The API returns at most 500 links per request, so pages with more links take several requests
+    ///
+    /// The default value is [None]
+    pub fn max_links(self, max_links: Option<usize>) -> Self {
+        Self { max_links, ..self }
+    }
+
+    /// Sets the most pages fetched when getting the backlinks of a page, [None] fetches every backlink
+    ///
+    /// The API returns at most 500 backlinks per request, so popular pages take several requests
+    ///
+    /// The default value is [None]
+    pub fn max_backlinks(self, max_backlinks: Option<usize>) -> Self {
+        Self {
+            max_backlinks,
+            ..self
+        }
+    }
+
+    /// Sets the cache the client checks for page bodies before sending a request
+    ///
+    /// Bodies are stored per language, URL type and title. Clones of a client share the same cache
+    ///
+    /// The default value is no cache
+    pub fn cache(self, cache: impl PageCache + 'static) -> Self {
+        Self {
+            cache: Some(Arc::new(cache)),
+            ..self
+        }
+    }
+
+    /// Sets if the client is offline, in which case no requests are sent
+    ///
+    /// An offline client only loads pages from its cache, anything else fails with an error whose [HttpError::kind] is [HttpError::PageNotFound]
+    ///
+    /// The default value is false
+    pub fn offline(self, offline: bool) -> Self {
+        Self { offline, ..self }
+    }
+
     /// Returns the headers of the client config
     pub fn headers(&self) -> &HeaderMap {
         &self.headers
@@ -107,8 +285,20 @@ impl Default for WikipediaClientConfig {
 
         WikipediaClientConfig {
             language: WikiLanguage::from_code("en").expect("Language 'en' does not exist"),
+            project: WikiProject::Wikipedia,
             headers,
             url_type: WikipediaUrlType::RawApi,
+            transport: Arc::new(EhttpTransport),
+            timeout: Some(Duration::from_secs(30)),
+            max_retries: 3,
+            backoff: BackoffPolicy::default(),
+            rate_limit: Some(RateLimit::new(20.0, 20)),
+            max_in_flight: Some(10),
+            max_redirects: CLIENT_REDIRECTS,
+            max_links: None,
+            max_backlinks: None,
+            cache: None,
+            offline: false,
         }
         .user_agent(USER_AGENT)
         .expect("Default headers are invalid")
@@ -121,6 +311,8 @@ impl Default for WikipediaClientConfig {
 trait WikipediaClientCommon {
     fn language(&self) -> WikiLanguage;
 
+    fn project(&self) -> WikiProject;
+
     fn url_from_pathinfo<T: std::fmt::Display>(
         &self,
         pathinfo: T,
@@ -128,14 +320,14 @@ trait WikipediaClientCommon {
     ) -> Result<Url, WikipediaLanguageInvalidError> {
         let pathinfo = pathinfo.to_string();
 
-        url_type.url_with(self.language(), &pathinfo)
+        url_type.url_with(self.project(), self.language(), &pathinfo)
     }
 }
 
 #[cfg(test)]
 mod test {
     mod language {
-        use crate::WikiLanguage;
+        use crate::{WikiLanguage, WikiProject};
 
         use crate::page::WikipediaUrlType;
 
@@ -167,13 +359,15 @@ mod test {
             for (code, name) in TEST_LANGUAGES {
                 let url = WikipediaUrlType::Basic
                     .base_url(
+                        WikiProject::Wikipedia,
                         WikiLanguage::from_code(code)
-                            .expect(format!("Wikipedia code '{code}' is invalid").as_str()),
+                            .unwrap_or_else(|| panic!("Wikipedia code '{code}' is invalid")),
                     )
-                    .expect(format!("Language '{name}' has no wikipedia code").as_str());
-                if !url.host_str().map_or(false, |host| {
-                    host.starts_with(code) && host.ends_with("wikipedia.org")
-                }) {
+                    .unwrap_or_else(|_| panic!("Language '{name}' has no wikipedia code"));
+                if !url
+                    .host_str()
+                    .is_some_and(|host| host.starts_with(code) && host.ends_with("wikipedia.org"))
+                {
                     panic!("Url does not start with the correct wikipedia language code")
                 }
             }
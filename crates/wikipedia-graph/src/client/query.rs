@@ -0,0 +1,234 @@
+use serde_json::{Map, Value};
+
+/// What a continued query returns, which decides how its responses are merged
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum QueryKind {
+    /// A `prop=...` query, where every page in `query.pages` has its own list at `{name}`, like `links`
+    Prop(&'static str),
+    /// A `list=...` query, where every result is in a single list at `query.{name}`
+    List(&'static str),
+}
+
+impl QueryKind {
+    /// Merge a continued response into the responses before it
+    pub(crate) fn merge(self, merged: &mut Value, continued: Value) {
+        match self {
+            Self::Prop(name) => merge_continued(merged, continued, name),
+            Self::List(name) => merge_continued_list(merged, continued, name),
+        }
+    }
+
+    /// Cut the results down to the limit, returns true if the limit was reached
+    pub(crate) fn truncate(self, response: &mut Value, max: usize) -> bool {
+        match self {
+            Self::Prop(name) => truncate_prop(response, max, name),
+            Self::List(name) => response
+                .get_mut("query")
+                .and_then(|query| query.get_mut(name)?.as_array_mut())
+                .is_some_and(|list| {
+                    list.truncate(max);
+                    list.len() == max
+                }),
+        }
+    }
+}
+
+/// Get the parameters needed to continue a query, if the response is incomplete
+///
+/// The API sends these as `{continue: {plcontinue: "...", continue: "||"}}`
+pub(crate) fn continue_params(response: &Value) -> Option<Vec<(String, String)>> {
+    response.get("continue")?.as_object().map(|params| {
+        params
+            .iter()
+            .map(|(name, value)| {
+                let value = match value {
+                    Value::String(value) => value.clone(),
+                    value => value.to_string(),
+                };
+
+                (name.clone(), value)
+            })
+            .collect()
+    })
+}
+
+fn pages_mut(response: &mut Value) -> Option<&mut Map<String, Value>> {
+    response.get_mut("query")?.get_mut("pages")?.as_object_mut()
+}
+
+/// Replace the continuation of the merged responses with the continued response's, so the query can be continued again
+fn replace_continue(merged: &mut Value, continued: &Value) {
+    if let Some(merged) = merged.as_object_mut() {
+        match continued.get("continue") {
+            Some(params) => merged.insert(String::from("continue"), params.clone()),
+            None => merged.remove("continue"),
+        };
+    }
+}
+
+/// Append the results of a continued list query to the list of the first response
+fn merge_continued_list(merged: &mut Value, mut continued: Value, name: &str) {
+    replace_continue(merged, &continued);
+
+    let Some(continued_list) = continued
+        .get_mut("query")
+        .and_then(|query| query.get_mut(name)?.as_array_mut())
+        .map(std::mem::take)
+    else {
+        return;
+    };
+
+    if let Some(list) = merged
+        .get_mut("query")
+        .and_then(|query| query.get_mut(name)?.as_array_mut())
+    {
+        list.extend(continued_list);
+    }
+}
+
+/// Append the results of every page in a continued response to the pages of the first response
+fn merge_continued(merged: &mut Value, mut continued: Value, name: &str) {
+    replace_continue(merged, &continued);
+
+    let Some(continued_pages) = pages_mut(&mut continued).map(std::mem::take) else {
+        return;
+    };
+
+    let Some(pages) = pages_mut(merged) else {
+        return;
+    };
+
+    for (id, mut continued_page) in continued_pages {
+        let Some(page) = pages.get_mut(&id) else {
+            pages.insert(id, continued_page);
+            continue;
+        };
+
+        let Some(continued_links) = continued_page
+            .get_mut(name)
+            .and_then(Value::as_array_mut)
+            .map(std::mem::take)
+        else {
+            continue;
+        };
+
+        match page.get_mut(name).and_then(Value::as_array_mut) {
+            Some(links) => links.extend(continued_links),
+            None => {
+                if let Some(page) = page.as_object_mut() {
+                    page.insert(String::from(name), Value::Array(continued_links));
+                }
+            }
+        }
+    }
+}
+
+/// Cut the results of every page down to the limit, returns true if every page reached it
+fn truncate_prop(response: &mut Value, max_links: usize, name: &str) -> bool {
+    pages_mut(response).is_some_and(|pages| {
+        pages.values_mut().fold(true, |all_full, page| {
+            let full = match page.get_mut(name).and_then(Value::as_array_mut) {
+                Some(links) => {
+                    links.truncate(max_links);
+                    links.len() == max_links
+                }
+                None => max_links == 0,
+            };
+
+            all_full && full
+        })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn merge_continued_links() {
+        let mut merged = json!({
+            "continue": { "plcontinue": "2|0|C", "continue": "||" },
+            "query": { "pages": {
+                "1": { "title": "A", "links": [{ "title": "A1" }] },
+                "2": { "title": "B", "links": [{ "title": "B1" }] },
+                "3": { "title": "C" }
+            } }
+        });
+
+        assert_eq!(
+            continue_params(&merged),
+            Some(vec![
+                (String::from("continue"), String::from("||")),
+                (String::from("plcontinue"), String::from("2|0|C"))
+            ])
+        );
+
+        QueryKind::Prop("links").merge(
+            &mut merged,
+            json!({
+                "batchcomplete": "",
+                "query": { "pages": {
+                    "1": { "title": "A" },
+                    "2": { "title": "B", "links": [{ "title": "B2" }] },
+                    "3": { "title": "C", "links": [{ "title": "C1" }] }
+                } }
+            }),
+        );
+
+        assert_eq!(continue_params(&merged), None);
+        assert_eq!(
+            merged["query"]["pages"],
+            json!({
+                "1": { "title": "A", "links": [{ "title": "A1" }] },
+                "2": { "title": "B", "links": [{ "title": "B1" }, { "title": "B2" }] },
+                "3": { "title": "C", "links": [{ "title": "C1" }] }
+            })
+        );
+
+        assert!(!QueryKind::Prop("links").truncate(&mut merged, 2));
+        assert!(QueryKind::Prop("links").truncate(&mut merged, 1));
+        assert_eq!(
+            merged["query"]["pages"]["2"]["links"],
+            json!([{ "title": "B1" }])
+        );
+
+        let mut merged = json!({
+            "continue": { "llcontinue": "1|fr", "continue": "||" },
+            "query": { "pages": { "1": { "title": "A", "langlinks": [{ "lang": "de", "*": "A" }] } } }
+        });
+
+        QueryKind::Prop("langlinks").merge(
+            &mut merged,
+            json!({ "query": { "pages": { "1": { "title": "A", "langlinks": [{ "lang": "fr", "*": "A" }] } } } }),
+        );
+
+        assert_eq!(
+            merged["query"]["pages"]["1"]["langlinks"],
+            json!([{ "lang": "de", "*": "A" }, { "lang": "fr", "*": "A" }])
+        );
+    }
+
+    #[test]
+    fn merge_continued_list() {
+        let kind = QueryKind::List("backlinks");
+
+        let mut merged = json!({
+            "continue": { "blcontinue": "0|2", "continue": "-||" },
+            "query": { "backlinks": [{ "title": "A" }] }
+        });
+
+        kind.merge(
+            &mut merged,
+            json!({ "query": { "backlinks": [{ "title": "B" }, { "title": "C" }] } }),
+        );
+
+        assert_eq!(continue_params(&merged), None);
+        assert_eq!(
+            merged["query"]["backlinks"],
+            json!([{ "title": "A" }, { "title": "B" }, { "title": "C" }])
+        );
+        assert!(kind.truncate(&mut merged, 2));
+        assert_eq!(merged["query"]["backlinks"].as_array().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,233 @@
+use super::batch::{self, MAX_BATCH_TITLES};
+use super::future::{join_all, spawn_with_callback};
+use super::{HttpError, LoadedBody, WikipediaClient};
+use crate::WikipediaPage;
+use crate::page::{WikipediaBody, WikipediaLanguageInvalidError, WikipediaUrlType};
+use http::StatusCode;
+use itertools::Itertools;
+use std::future::Future;
+use std::pin::Pin;
+
+/// The outcome of [WikipediaClient::refresh], with every page sorted by whether it changed
+///
+/// *This struct requires the `client` feature*
+#[derive(Clone, Debug, Default)]
+pub struct RefreshReport {
+    updated: Vec<WikipediaPage>,
+    unchanged: Vec<WikipediaPage>,
+    failed: Vec<(WikipediaPage, HttpError)>,
+}
+
+impl RefreshReport {
+    /// The pages that changed, with their new bodies loaded
+    pub fn updated(&self) -> &[WikipediaPage] {
+        &self.updated
+    }
+
+    /// The pages that didn't change, exactly as they were passed in
+    pub fn unchanged(&self) -> &[WikipediaPage] {
+        &self.unchanged
+    }
+
+    /// The pages that couldn't be checked or loaded again, along with the error
+    pub fn failed(&self) -> &[(WikipediaPage, HttpError)] {
+        &self.failed
+    }
+}
+
+/// What a single page turned out to be after checking it
+enum Refreshed {
+    Updated(WikipediaPage),
+    Unchanged(WikipediaPage),
+    Failed(WikipediaPage, HttpError),
+}
+
+type RefreshFuture = Pin<Box<dyn Future<Output = Vec<Refreshed>> + Send>>;
+
+impl WikipediaClient {
+    /// Check if loaded pages changed since they were loaded, and load the pages that did again
+    ///
+    /// Executes the given callback once every page has been checked
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the client's language has no valid URL, failures of single pages are reported in the [RefreshReport]
+    pub fn refresh(
+        &self,
+        pages: &[WikipediaPage],
+        callback: impl Fn(RefreshReport) + Send + 'static,
+    ) -> Result<(), WikipediaLanguageInvalidError> {
+        WikipediaUrlType::LinksApi.base_url(self.project(), self.language())?;
+
+        spawn_with_callback(self.refresh_async(pages), callback);
+
+        Ok(())
+    }
+
+    /// Check if loaded pages changed since they were loaded, and load the pages that did again
+    ///
+    /// Pages with a known revision are checked with one `prop=info` request per [MAX_BATCH_TITLES] pages,
+    /// pages with only an `ETag` are requested with `If-None-Match`, and any other page is always loaded again.
+    /// Only the bodies of pages that changed are downloaded, which also replaces them in the client's cache
+    pub fn refresh_async(
+        &self,
+        pages: &[WikipediaPage],
+    ) -> impl Future<Output = RefreshReport> + Send + 'static {
+        let (by_revision, rest): (Vec<WikipediaPage>, Vec<WikipediaPage>) = pages
+            .iter()
+            .cloned()
+            .partition(|page| page.revision().is_some());
+
+        // Pages are checked on the wiki of their own project and language
+        let mut checks: Vec<RefreshFuture> = by_revision
+            .into_iter()
+            .into_group_map_by(|page| (page.project(), page.language()))
+            .into_iter()
+            .flat_map(|((project, language), pages)| {
+                let client = self.for_wiki(project, language);
+
+                pages
+                    .chunks(MAX_BATCH_TITLES)
+                    .map(|pages| {
+                        Box::pin(client.refresh_by_revision(pages.to_vec())) as RefreshFuture
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        checks.extend(rest.into_iter().map(|page| {
+            let client = self.for_wiki(page.project(), page.language());
+
+            match page.etag() {
+                Some(_) => Box::pin(client.refresh_by_etag(page)) as RefreshFuture,
+                None => Box::pin(client.reload(page)) as RefreshFuture,
+            }
+        }));
+
+        async move {
+            let mut report = RefreshReport::default();
+
+            for refreshed in join_all(checks).await.into_iter().flatten() {
+                match refreshed {
+                    Refreshed::Updated(page) => report.updated.push(page),
+                    Refreshed::Unchanged(page) => report.unchanged.push(page),
+                    Refreshed::Failed(page, err) => report.failed.push((page, err)),
+                }
+            }
+
+            report
+        }
+    }
+
+    /// Compare the revisions of up to [MAX_BATCH_TITLES] pages with their latest revisions, loading the outdated pages again
+    fn refresh_by_revision(
+        &self,
+        pages: Vec<WikipediaPage>,
+    ) -> impl Future<Output = Vec<Refreshed>> + Send + 'static {
+        let client = self.clone();
+
+        async move {
+            let titles: Vec<String> = pages.iter().map(WikipediaPage::title).collect();
+
+            let latest =
+                match WikipediaUrlType::info_url(client.project(), client.language(), &titles) {
+                    Ok(url) => client.fetch_parsed(ehttp::Request::get(url.as_str())).await,
+                    Err(err) => Err(err.into()),
+                };
+
+            let latest = match latest {
+                Ok(latest) => batch::split_batch(&titles, &latest),
+                Err(err) => batch::all_failed(&titles, &err),
+            };
+
+            let outdated = pages
+                .into_iter()
+                .zip(latest)
+                .map(|(page, (_, latest))| match latest {
+                    Ok((info, _)) if info.revision() == page.revision() => {
+                        Box::pin(async move { vec![Refreshed::Unchanged(page)] }) as RefreshFuture
+                    }
+                    Ok(_) => Box::pin(client.reload(page)) as RefreshFuture,
+                    Err(err) => {
+                        Box::pin(async move { vec![Refreshed::Failed(page, err)] }) as RefreshFuture
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            join_all(outdated).await.into_iter().flatten().collect()
+        }
+    }
+
+    /// Request a page with `If-None-Match`, using the new body unless the server responds with 304 Not Modified
+    fn refresh_by_etag(
+        &self,
+        page: WikipediaPage,
+    ) -> impl Future<Output = Vec<Refreshed>> + Send + 'static {
+        let client = self.clone();
+
+        async move {
+            let mut request = match client.request_from_pathinfo(page.pathinfo(), client.url_type())
+            {
+                Ok(request) => request,
+                Err(err) => return vec![Refreshed::Failed(page, err.into())],
+            };
+
+            if let Some(etag) = page.etag() {
+                request
+                    .headers
+                    .insert(http::header::IF_NONE_MATCH.as_str(), etag);
+            }
+
+            let url_type = client.url_type();
+
+            // The body is only read if the page changed, an unchanged page resolves with `None`
+            let response = client.fetch_with(request, move |response| {
+                if response.status == StatusCode::NOT_MODIFIED.as_u16() {
+                    return Ok(None);
+                }
+
+                let etag = response
+                    .headers
+                    .get(http::header::ETAG.as_str())
+                    .map(ToString::to_string);
+
+                let body = WikipediaBody::from_url_type(
+                    url_type,
+                    WikipediaClient::response_text(&response)?,
+                )?;
+
+                Ok(Some((body, etag)))
+            });
+
+            match response.await {
+                Ok(None) => vec![Refreshed::Unchanged(page)],
+                // A page that became a redirect is loaded from its new target instead
+                Ok(Some((body, _))) if body.redirects_to().is_some() => client.reload(page).await,
+                Ok(Some((body, etag))) => {
+                    client.store(client.url_type(), page.pathinfo(), &body);
+
+                    let loaded = LoadedBody {
+                        body,
+                        redirects: Vec::new(),
+                        etag,
+                    };
+
+                    vec![Refreshed::Updated(page.with_loaded(loaded))]
+                }
+                Err(err) => vec![Refreshed::Failed(page, err)],
+            }
+        }
+    }
+
+    /// Load a page again without checking the cache first
+    fn reload(&self, page: WikipediaPage) -> impl Future<Output = Vec<Refreshed>> + Send + 'static {
+        let response = self.get_with_redirects_async(page.pathinfo().clone(), false);
+
+        async move {
+            vec![match response.await {
+                Ok(loaded) => Refreshed::Updated(page.with_loaded(loaded)),
+                Err(err) => Refreshed::Failed(page, err),
+            }]
+        }
+    }
+}
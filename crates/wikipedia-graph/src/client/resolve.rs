@@ -0,0 +1,253 @@
+use super::batch::{self, MAX_BATCH_TITLES};
+use super::future::{join_all, spawn_with_callback};
+use super::{HttpError, WikipediaClient};
+use crate::page::{WikipediaLanguageInvalidError, WikipediaUrlType};
+use crate::response::{QueryResponse, ResponseError};
+use crate::{Title, WikiLanguage, WikiProject};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::future::Future;
+
+/// The canonical titles of several titles, as resolved by the API, see [WikipediaClient::resolve_titles]
+///
+/// Unlike [Title], this knows about redirects and the capitalization rules of the wiki
+///
+/// *This struct requires the `client` feature*
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResolvedTitles {
+    project: WikiProject,
+    language: WikiLanguage,
+    canonical: HashMap<String, Title>,
+    missing: HashSet<String>,
+}
+
+impl ResolvedTitles {
+    /// Resolved titles on a wiki, without any titles yet
+    fn new(project: WikiProject, language: WikiLanguage) -> Self {
+        Self {
+            project,
+            language,
+            canonical: HashMap::new(),
+            missing: HashSet::new(),
+        }
+    }
+
+    /// Get the project of the wiki the titles were resolved on
+    pub fn project(&self) -> WikiProject {
+        self.project
+    }
+
+    /// Get the language of the wiki the titles were resolved on
+    pub fn language(&self) -> WikiLanguage {
+        self.language
+    }
+
+    /// Get the canonical title of a title that was resolved, following its redirect if it has one
+    ///
+    /// Missing pages still have a canonical title, which is the title after normalization
+    pub fn canonical(&self, title: &str) -> Option<&Title> {
+        self.canonical.get(title)
+    }
+
+    /// Get every resolved title along with its canonical title
+    pub fn canonical_titles(&self) -> &HashMap<String, Title> {
+        &self.canonical
+    }
+
+    /// Get the resolved titles of pages that don't exist or aren't valid titles, as they were passed in
+    pub fn missing(&self) -> &HashSet<String> {
+        &self.missing
+    }
+
+    /// Check if a resolved title belongs to a page that doesn't exist
+    pub fn is_missing(&self, title: &str) -> bool {
+        self.missing.contains(title)
+    }
+
+    /// Add the titles resolved by another batch
+    fn extend(&mut self, other: ResolvedTitles) {
+        self.canonical.extend(other.canonical);
+        self.missing.extend(other.missing);
+    }
+
+    /// Resolve titles with the response of a query for them
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the response has no `query.pages` field
+    pub(crate) fn from_response(
+        project: WikiProject,
+        language: WikiLanguage,
+        titles: &[String],
+        response: &QueryResponse,
+    ) -> Result<Self, ResponseError> {
+        let query = response.query()?;
+
+        let normalized = batch::title_map(&query.normalized);
+        let redirects = batch::title_map(&query.redirects);
+
+        let missing: HashSet<&str> = query
+            .pages()?
+            .iter()
+            .filter(|(_, page)| batch::page_error(page).is_some())
+            .map(|(_, page)| page.title.as_str())
+            .collect();
+
+        let mut resolved = Self::new(project, language);
+
+        for title in titles {
+            let mut canonical = normalized
+                .get(title.as_str())
+                .copied()
+                .unwrap_or(title.as_str());
+
+            // Redirects to redirects are listed one after another, a loop ends where it started
+            let mut followed = vec![canonical];
+
+            while let Some(target) = redirects.get(canonical)
+                && !followed.contains(target)
+            {
+                canonical = target;
+
+                followed.push(target);
+            }
+
+            if missing.contains(canonical) {
+                resolved.missing.insert(title.clone());
+            }
+
+            resolved
+                .canonical
+                .insert(title.clone(), Title::for_project(canonical, project));
+        }
+
+        Ok(resolved)
+    }
+}
+
+impl WikipediaClient {
+    /// Resolve many titles to their canonical titles, following redirects
+    ///
+    /// Executes the given callback once every title has been resolved
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the client's language has no valid URL
+    pub fn resolve_titles<T: Display>(
+        &self,
+        titles: impl IntoIterator<Item = T>,
+        callback: impl Fn(Result<ResolvedTitles, HttpError>) + Send + 'static,
+    ) -> Result<(), WikipediaLanguageInvalidError> {
+        WikipediaUrlType::LinksApi.base_url(self.project(), self.language())?;
+
+        spawn_with_callback(self.resolve_titles_async(titles), callback);
+
+        Ok(())
+    }
+
+    /// Resolve many titles to their canonical titles, following redirects
+    ///
+    /// Sends one `redirects=1` query per [MAX_BATCH_TITLES] titles, without downloading any page
+    ///
+    /// # Errors
+    ///
+    /// This method fails if any of the requests fail
+    pub fn resolve_titles_async<T: Display>(
+        &self,
+        titles: impl IntoIterator<Item = T>,
+    ) -> impl Future<Output = Result<ResolvedTitles, HttpError>> + Send + 'static {
+        let titles: Vec<String> = titles.into_iter().map(|title| title.to_string()).collect();
+
+        let (project, language) = (self.project(), self.language());
+
+        let batches = titles
+            .chunks(MAX_BATCH_TITLES)
+            .map(|titles| {
+                let titles = titles.to_vec();
+
+                let request =
+                    WikipediaUrlType::resolve_url(self.project(), self.language(), &titles)
+                        .map(|url| self.fetch_parsed(ehttp::Request::get(url.as_str())));
+
+                async move {
+                    let body: QueryResponse = request?.await?;
+
+                    Ok::<_, HttpError>(ResolvedTitles::from_response(
+                        project, language, &titles, &body,
+                    )?)
+                }
+            })
+            .collect::<Vec<_>>();
+
+        async move {
+            let mut resolved = ResolvedTitles::new(project, language);
+
+            for batch in join_all(batches).await {
+                resolved.extend(batch?);
+            }
+
+            Ok(resolved)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn resolve_response() {
+        let titles = vec![
+            String::from("usa"),
+            String::from("Waffle"),
+            String::from("US"),
+            String::from("Not a page"),
+        ];
+
+        let body: QueryResponse = serde_json::from_value(serde_json::json!({
+            "query": {
+                "normalized": [{ "from": "usa", "to": "USA" }],
+                "redirects": [
+                    { "from": "USA", "to": "United States" },
+                    { "from": "US", "to": "U.S." },
+                    { "from": "U.S.", "to": "United States" }
+                ],
+                "pages": {
+                    "1": { "pageid": 1, "ns": 0, "title": "United States" },
+                    "2": { "pageid": 2, "ns": 0, "title": "Waffle" },
+                    "-1": { "ns": 0, "title": "Not a page", "missing": "" }
+                }
+            }
+        }))
+        .unwrap();
+
+        let resolved = ResolvedTitles::from_response(
+            WikiProject::Wikipedia,
+            WikiLanguage::English,
+            &titles,
+            &body,
+        )
+        .unwrap();
+
+        let united_states = Title::new("United States");
+
+        assert_eq!(resolved.canonical("usa"), Some(&united_states));
+        assert_eq!(resolved.canonical("US"), Some(&united_states));
+        assert_eq!(resolved.canonical("Waffle"), Some(&Title::new("Waffle")));
+        assert_eq!(resolved.canonical("Canada"), None);
+        assert!(resolved.is_missing("Not a page"));
+        assert_eq!(resolved.missing().len(), 1);
+
+        let empty: QueryResponse = serde_json::from_value(serde_json::json!({})).unwrap();
+
+        assert!(matches!(
+            ResolvedTitles::from_response(
+                WikiProject::Wikipedia,
+                WikiLanguage::English,
+                &titles,
+                &empty,
+            ),
+            Err(ResponseError::MissingField("query"))
+        ));
+    }
+}
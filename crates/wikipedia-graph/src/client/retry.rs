@@ -0,0 +1,153 @@
+use web_time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// How long a client waits before retrying a failed request
+///
+/// A `Retry-After` header sent by the server takes precedence over the policy, but is never waited on longer than [BackoffPolicy::max_delay]
+///
+/// *This enum requires the `client` feature*
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BackoffPolicy {
+    /// Wait the same amount of time before every retry
+    Constant(Duration),
+    /// Wait `initial * factor ^ retry` before every retry, never waiting longer than `max`
+    Exponential {
+        /// The delay before the first retry
+        initial: Duration,
+        /// The amount the delay is multiplied by after each retry
+        factor: f64,
+        /// The longest possible delay
+        max: Duration,
+    },
+}
+
+impl Default for BackoffPolicy {
+    fn default() -> Self {
+        Self::Exponential {
+            initial: Duration::from_millis(500),
+            factor: 2.0,
+            max: Duration::from_secs(30),
+        }
+    }
+}
+
+impl BackoffPolicy {
+    /// Get the delay before a retry, where the first retry is `0`
+    pub fn delay(&self, retry: u32) -> Duration {
+        match self {
+            Self::Constant(delay) => *delay,
+            Self::Exponential {
+                initial,
+                factor,
+                max,
+            } => {
+                let exponent = i32::try_from(retry).unwrap_or(i32::MAX);
+
+                Duration::try_from_secs_f64(initial.as_secs_f64() * factor.powi(exponent))
+                    .map_or(*max, |delay| delay.min(*max))
+            }
+        }
+    }
+
+    /// Get the longest delay before a retry
+    pub fn max_delay(&self) -> Duration {
+        match self {
+            Self::Constant(delay) => *delay,
+            Self::Exponential { max, .. } => *max,
+        }
+    }
+}
+
+/// Check if a response with this status code is worth retrying
+pub(crate) fn is_retryable_status(status: u16) -> bool {
+    status == 429 || (500..600).contains(&status)
+}
+
+/// Parse the value of a `Retry-After` header
+///
+/// The header is either a number of seconds or an HTTP date, like `Wed, 21 Oct 2015 07:28:00 GMT`
+pub(crate) fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = UNIX_EPOCH + Duration::from_secs(parse_http_date(value)?);
+
+    Some(
+        date.duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+/// Parse an IMF-fixdate into seconds since the unix epoch
+fn parse_http_date(value: &str) -> Option<u64> {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    let mut parts = value.split_whitespace().skip(1);
+
+    let day: u64 = parts.next()?.parse().ok()?;
+    let month = parts.next()?;
+    let month = MONTHS.iter().position(|name| *name == month)? as u64 + 1;
+    let year: u64 = parts.next()?.parse().ok()?;
+
+    let mut time = parts
+        .next()?
+        .split(':')
+        .map(|part| part.parse::<u64>().ok());
+    let (hour, minute, second) = (time.next()??, time.next()??, time.next()??);
+
+    if parts.next()? != "GMT" || year < 1970 {
+        return None;
+    }
+
+    // Days since the epoch, from Howard Hinnant's `days_from_civil`
+    let (year, month) = if month <= 2 {
+        (year - 1, month + 9)
+    } else {
+        (year, month - 3)
+    };
+    let era = year / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * month + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = (era * 146_097 + day_of_era).checked_sub(719_468)?;
+
+    Some(days * 86400 + hour * 3600 + minute * 60 + second)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn exponential_backoff() {
+        let policy = BackoffPolicy::Exponential {
+            initial: Duration::from_secs(1),
+            factor: 2.0,
+            max: Duration::from_secs(5),
+        };
+
+        assert_eq!(policy.delay(0), Duration::from_secs(1));
+        assert_eq!(policy.delay(2), Duration::from_secs(4));
+        assert_eq!(policy.delay(3), Duration::from_secs(5));
+        assert_eq!(policy.delay(u32::MAX), Duration::from_secs(5));
+        assert_eq!(policy.max_delay(), Duration::from_secs(5));
+    }
+
+    #[test]
+    fn retry_after() {
+        assert_eq!(parse_retry_after(" 120 "), Some(Duration::from_secs(120)));
+        assert_eq!(
+            parse_http_date("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(1_445_412_480)
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon"), None);
+    }
+}
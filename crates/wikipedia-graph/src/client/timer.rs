@@ -0,0 +1,120 @@
+use super::future::{ResponseFuture, oneshot};
+use web_time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use thread::after;
+
+/// Timers on a background thread, which needs memory shared with the main thread
+#[cfg(not(target_arch = "wasm32"))]
+mod thread {
+    use std::cmp::Ordering;
+    use std::collections::BinaryHeap;
+    use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender, channel};
+    use std::sync::{Mutex, OnceLock};
+    use web_time::{Duration, Instant};
+
+    type TimerCallback = Box<dyn FnOnce() + Send>;
+
+    struct TimerEntry {
+        deadline: Instant,
+        callback: TimerCallback,
+    }
+
+    impl PartialEq for TimerEntry {
+        fn eq(&self, other: &Self) -> bool {
+            self.deadline == other.deadline
+        }
+    }
+
+    impl Eq for TimerEntry {}
+
+    impl PartialOrd for TimerEntry {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for TimerEntry {
+        // Reversed so the binary heap pops the earliest deadline first
+        fn cmp(&self, other: &Self) -> Ordering {
+            other.deadline.cmp(&self.deadline)
+        }
+    }
+
+    /// A single background thread shared by every timeout and delay in the crate
+    static TIMER: OnceLock<Mutex<Sender<TimerEntry>>> = OnceLock::new();
+
+    fn run_timer(reciever: &Receiver<TimerEntry>) {
+        let mut entries: BinaryHeap<TimerEntry> = BinaryHeap::new();
+
+        loop {
+            let now = Instant::now();
+
+            while entries.peek().is_some_and(|entry| entry.deadline <= now) {
+                if let Some(entry) = entries.pop() {
+                    (entry.callback)();
+                }
+            }
+
+            let next = match entries.peek() {
+                Some(entry) => reciever.recv_timeout(entry.deadline.saturating_duration_since(now)),
+                None => reciever.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+
+            match next {
+                Ok(entry) => entries.push(entry),
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+    }
+
+    /// Run the callback on the timer thread once the duration has passed
+    pub(crate) fn after(duration: Duration, callback: impl FnOnce() + Send + 'static) {
+        let sender = TIMER.get_or_init(|| {
+            let (sender, reciever) = channel();
+
+            std::thread::Builder::new()
+                .name(String::from("wikipedia-graph-timer"))
+                .spawn(move || run_timer(&reciever))
+                .expect("Failed to spawn the timer thread");
+
+            Mutex::new(sender)
+        });
+
+        let entry = TimerEntry {
+            deadline: Instant::now() + duration,
+            callback: Box::new(callback),
+        };
+
+        let sent = match sender.lock() {
+            Ok(sender) => sender.send(entry),
+            Err(e) => e.get_ref().send(entry),
+        };
+
+        if sent.is_err() {
+            log::error!("Timer thread has stopped");
+        }
+    }
+}
+
+/// Run the callback once the duration has passed
+///
+/// Without atomics, wasm32 can't share memory with a thread, so the browser's `setTimeout` runs it instead
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn after(duration: Duration, callback: impl FnOnce() + Send + 'static) {
+    let millis = u32::try_from(duration.as_millis()).unwrap_or(u32::MAX);
+
+    gloo_timers::callback::Timeout::new(millis, callback).forget();
+}
+
+/// A future that resolves once the duration has passed
+pub(crate) fn sleep(duration: Duration) -> ResponseFuture<()> {
+    let (completer, future) = oneshot();
+
+    after(duration, move || {
+        completer.complete(());
+    });
+
+    future
+}
@@ -0,0 +1,143 @@
+use super::future::lock;
+use ehttp::{Headers, Request, Response};
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+
+/// The callback a [HttpTransport] runs once a request has finished
+pub type TransportCallback = Box<dyn FnOnce(Result<Response, String>) + Send + 'static>;
+
+/// The backend used by a [crate::WikipediaClient] to send its requests
+///
+/// A transport takes a request and eventually calls the callback with the status, headers and body of the response.
+/// An `Err` should only be returned if no response could be received at all, error status codes are handled by the client.
+///
+/// *This trait requires the `client` feature*
+pub trait HttpTransport: Debug + Send + Sync {
+    /// Send a request and run the callback upon completion
+    fn fetch(&self, request: Request, callback: TransportCallback);
+}
+
+/// The default transport, sends all requests through [ehttp::fetch]
+///
+/// *This struct requires the `client` feature*
+#[derive(Clone, Copy, Debug, Default)]
+pub struct EhttpTransport;
+
+impl HttpTransport for EhttpTransport {
+    fn fetch(&self, request: Request, callback: TransportCallback) {
+        ehttp::fetch(request, callback);
+    }
+}
+
+/// A canned response served by a [FixtureTransport]
+#[derive(Clone, Debug)]
+struct FixtureResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+/// An in-memory transport that serves canned responses keyed by URL
+///
+/// Responses added for the same URL are served in order, with the last one being repeated.
+/// Any URL without a response returns a 404
+///
+/// *This struct requires the `client` feature*
+#[derive(Clone, Debug, Default)]
+pub struct FixtureTransport {
+    responses: Arc<Mutex<HashMap<String, VecDeque<FixtureResponse>>>>,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl FixtureTransport {
+    /// Create a transport without any responses
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serve the body with a 200 status code whenever the URL is requested
+    pub fn with_response(self, url: impl std::fmt::Display, body: impl Into<String>) -> Self {
+        self.with_status(url, 200, body)
+    }
+
+    /// Serve the body with the provided status code whenever the URL is requested
+    pub fn with_status(
+        self,
+        url: impl std::fmt::Display,
+        status: u16,
+        body: impl Into<String>,
+    ) -> Self {
+        self.with_headers(url, status, &[], body)
+    }
+
+    /// Serve the body with the provided status code and headers whenever the URL is requested
+    pub fn with_headers(
+        self,
+        url: impl std::fmt::Display,
+        status: u16,
+        headers: &[(&str, &str)],
+        body: impl Into<String>,
+    ) -> Self {
+        let response = FixtureResponse {
+            status,
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            body: body.into(),
+        };
+
+        lock(&self.responses)
+            .entry(url.to_string())
+            .or_default()
+            .push_back(response);
+
+        self
+    }
+
+    /// Get the URLs of all requests made through this transport, in order
+    ///
+    /// Clones of the transport share their request log
+    pub fn requests(&self) -> Vec<String> {
+        lock(&self.requests).clone()
+    }
+}
+
+impl HttpTransport for FixtureTransport {
+    fn fetch(&self, request: Request, callback: TransportCallback) {
+        lock(&self.requests).push(request.url.clone());
+
+        let response = {
+            let mut responses = lock(&self.responses);
+
+            responses.get_mut(&request.url).and_then(|queue| {
+                if queue.len() > 1 {
+                    queue.pop_front()
+                } else {
+                    queue.front().cloned()
+                }
+            })
+        }
+        .unwrap_or(FixtureResponse {
+            status: 404,
+            headers: Vec::new(),
+            body: String::new(),
+        });
+
+        let mut headers = Headers::new(&[("Content-Type", "application/json")]);
+
+        for (name, value) in response.headers {
+            headers.insert(name, value);
+        }
+
+        callback(Ok(Response {
+            url: request.url,
+            ok: (200..300).contains(&response.status),
+            status: response.status,
+            status_text: String::new(),
+            headers,
+            bytes: response.body.into_bytes(),
+        }));
+    }
+}
@@ -0,0 +1,417 @@
+use crate::{Link, LinkPlacement, NamespaceFilter, WikipediaPage};
+use regex::Regex;
+use thiserror::Error;
+
+/// Decides which linked pages are kept, see [crate::WikipediaBody::get_linked_pages_with]
+///
+/// Every filter keeps the pages it matches, so [DateFilter] keeps dates and `DateFilter.not()` removes them.
+/// Filters can be combined with [LinkFilter::and], [LinkFilter::or] and [LinkFilter::not],
+/// and any `Fn(&WikipediaPage) -> bool` is a filter too
+///
+/// Bodies compose every filter with the defaults: [DefaultLinkFilter::BLOCKED_TITLES] are always removed,
+/// and only articles are kept unless the filter chooses the namespaces itself, see [LinkFilter::chooses_namespaces]
+pub trait LinkFilter {
+    /// Check if a linked page is kept
+    fn allows(&self, page: &WikipediaPage) -> bool;
+
+    /// Check if the filter decides which namespaces are kept, like [NamespaceFilter]
+    ///
+    /// Otherwise only articles are kept, whatever the filter allows
+    fn chooses_namespaces(&self) -> bool {
+        false
+    }
+
+    /// Check if a link is kept, which can also depend on where the link was found in the article
+    ///
+    /// By default only the page the link leads to is checked with [LinkFilter::allows]
+    fn allows_link(&self, link: &Link) -> bool {
+        self.allows(link.page())
+    }
+
+    /// Keep only the pages both filters keep
+    fn and<F: LinkFilter>(self, other: F) -> And<Self, F>
+    where
+        Self: Sized,
+    {
+        And(self, other)
+    }
+
+    /// Keep the pages either filter keeps
+    fn or<F: LinkFilter>(self, other: F) -> Or<Self, F>
+    where
+        Self: Sized,
+    {
+        Or(self, other)
+    }
+
+    /// Keep only the pages this filter removes
+    fn not(self) -> Not<Self>
+    where
+        Self: Sized,
+    {
+        Not(self)
+    }
+}
+
+impl<F: Fn(&WikipediaPage) -> bool> LinkFilter for F {
+    fn allows(&self, page: &WikipediaPage) -> bool {
+        self(page)
+    }
+}
+
+/// A filter keeping the pages both filters keep, see [LinkFilter::and]
+#[derive(Clone, Debug)]
+pub struct And<A, B>(A, B);
+
+impl<A: LinkFilter, B: LinkFilter> LinkFilter for And<A, B> {
+    fn allows(&self, page: &WikipediaPage) -> bool {
+        self.0.allows(page) && self.1.allows(page)
+    }
+
+    fn allows_link(&self, link: &Link) -> bool {
+        self.0.allows_link(link) && self.1.allows_link(link)
+    }
+
+    fn chooses_namespaces(&self) -> bool {
+        self.0.chooses_namespaces() || self.1.chooses_namespaces()
+    }
+}
+
+/// A filter keeping the pages either filter keeps, see [LinkFilter::or]
+#[derive(Clone, Debug)]
+pub struct Or<A, B>(A, B);
+
+impl<A: LinkFilter, B: LinkFilter> LinkFilter for Or<A, B> {
+    fn allows(&self, page: &WikipediaPage) -> bool {
+        self.0.allows(page) || self.1.allows(page)
+    }
+
+    fn allows_link(&self, link: &Link) -> bool {
+        self.0.allows_link(link) || self.1.allows_link(link)
+    }
+
+    fn chooses_namespaces(&self) -> bool {
+        self.0.chooses_namespaces() || self.1.chooses_namespaces()
+    }
+}
+
+/// A filter keeping the pages another filter removes, see [LinkFilter::not]
+#[derive(Clone, Debug)]
+pub struct Not<A>(A);
+
+impl<A: LinkFilter> LinkFilter for Not<A> {
+    fn allows(&self, page: &WikipediaPage) -> bool {
+        !self.0.allows(page)
+    }
+
+    fn allows_link(&self, link: &Link) -> bool {
+        !self.0.allows_link(link)
+    }
+
+    fn chooses_namespaces(&self) -> bool {
+        self.0.chooses_namespaces()
+    }
+}
+
+impl LinkFilter for NamespaceFilter {
+    fn allows(&self, page: &WikipediaPage) -> bool {
+        NamespaceFilter::allows(self, &page.namespace())
+    }
+
+    fn chooses_namespaces(&self) -> bool {
+        true
+    }
+}
+
+/// Check if the defaults every filter is composed with keep a page, see [LinkFilter]
+fn defaults_allow(filter: &dyn LinkFilter, page: &WikipediaPage) -> bool {
+    !DefaultLinkFilter::BLOCKED_TITLES.contains(&page.title().as_str())
+        && (filter.chooses_namespaces() || page.namespace().is_main())
+}
+
+/// Check if a filter composed with the defaults keeps a link
+pub(crate) fn keeps_link(filter: &dyn LinkFilter, link: &Link) -> bool {
+    defaults_allow(filter, link.page()) && filter.allows_link(link)
+}
+
+/// The filter used when no other filter is given, keeping articles except for [DefaultLinkFilter::BLOCKED_TITLES]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultLinkFilter;
+
+impl DefaultLinkFilter {
+    /// Titles of articles that are linked from almost every page
+    pub const BLOCKED_TITLES: [&str; 1] = [
+        "Wayback Machine", // Almost all sources are linked to through the wayback machine
+    ];
+}
+
+impl LinkFilter for DefaultLinkFilter {
+    fn allows(&self, page: &WikipediaPage) -> bool {
+        page.namespace().is_main() && !Self::BLOCKED_TITLES.contains(&page.title().as_str())
+    }
+
+    fn chooses_namespaces(&self) -> bool {
+        true
+    }
+}
+
+/// A filter keeping pages by title, `TitleFilter::new([...]).not()` makes a custom blocklist
+///
+/// Titles are compared without case and with underscores treated as spaces
+#[derive(Clone, Debug, Default)]
+pub struct TitleFilter {
+    titles: Vec<String>,
+}
+
+impl TitleFilter {
+    /// Create a filter keeping the given titles
+    pub fn new<T: AsRef<str>>(titles: impl IntoIterator<Item = T>) -> Self {
+        Self {
+            titles: titles
+                .into_iter()
+                .map(|title| normalize(title.as_ref()))
+                .collect(),
+        }
+    }
+
+    /// Sets another title to keep
+    pub fn with(mut self, title: impl AsRef<str>) -> Self {
+        self.titles.push(normalize(title.as_ref()));
+
+        self
+    }
+}
+
+fn normalize(title: &str) -> String {
+    title.replace('_', " ").trim().to_lowercase()
+}
+
+impl LinkFilter for TitleFilter {
+    fn allows(&self, page: &WikipediaPage) -> bool {
+        self.titles.contains(&normalize(&page.title()))
+    }
+}
+
+/// An error for a pattern of a [RegexFilter] that isn't a valid regex, contains the reason
+#[derive(Error, Debug, Clone)]
+#[error("Invalid title pattern: {0}")]
+pub struct InvalidPatternError(String);
+
+/// A filter keeping pages with a title matching a regex
+#[derive(Clone, Debug)]
+pub struct RegexFilter {
+    regex: Regex,
+}
+
+impl RegexFilter {
+    /// Create a filter keeping the pages with a title the pattern matches
+    ///
+    /// Patterns use the syntax of the [regex](https://docs.rs/regex) crate
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the pattern isn't a valid regex
+    pub fn new(pattern: &str) -> Result<Self, InvalidPatternError> {
+        Regex::new(pattern)
+            .map(|regex| Self { regex })
+            .map_err(|err| InvalidPatternError(err.to_string()))
+    }
+}
+
+impl LinkFilter for RegexFilter {
+    fn allows(&self, page: &WikipediaPage) -> bool {
+        self.regex.is_match(&page.title())
+    }
+}
+
+/// A filter keeping pages about years and dates, like `1987`, `1980s`, `20th century`, `1987 in film` and `March 3`
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DateFilter;
+
+impl DateFilter {
+    const DATE_REGEX: &lazy_regex::Lazy<Regex> = lazy_regex::regex!(
+        r"^(?:(?:AD |BC )?\d{1,4}(?:s| BC| BCE| AD| CE)?(?: in .+)?|\d{1,2}(?:st|nd|rd|th) (?:century|millennium)(?: BC| BCE)?|(?:January|February|March|April|May|June|July|August|September|October|November|December) \d{1,2}|\d{1,2} (?:January|February|March|April|May|June|July|August|September|October|November|December))$"
+    );
+}
+
+impl LinkFilter for DateFilter {
+    fn allows(&self, page: &WikipediaPage) -> bool {
+        Self::DATE_REGEX.is_match(&page.title())
+    }
+}
+
+/// A filter keeping lists, like `List of Norwegian desserts`
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ListFilter;
+
+impl LinkFilter for ListFilter {
+    fn allows(&self, page: &WikipediaPage) -> bool {
+        let title = page.title();
+
+        title.starts_with("List of ") || title.starts_with("Lists of ")
+    }
+}
+
+/// A filter keeping disambiguation pages, like `Mercury (disambiguation)`
+///
+/// Only pages with `(disambiguation)` in their title are recognized
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DisambiguationFilter;
+
+impl LinkFilter for DisambiguationFilter {
+    fn allows(&self, page: &WikipediaPage) -> bool {
+        page.title().ends_with("(disambiguation)")
+    }
+}
+
+/// A filter keeping pages about identifiers used in citations, like `ISBN (identifier)` and `Doi (identifier)`
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IdentifierFilter;
+
+impl IdentifierFilter {
+    const IDENTIFIER_REGEX: &lazy_regex::Lazy<Regex> = lazy_regex::regex!(
+        r"(?i)^(?:.+ \(identifier\)|ISBN|ISSN|OCLC|PMID|PMC|Doi|Digital object identifier|Bibcode|ArXiv|JSTOR|S2CID|LCCN|Hdl)$"
+    );
+}
+
+impl LinkFilter for IdentifierFilter {
+    fn allows(&self, page: &WikipediaPage) -> bool {
+        Self::IDENTIFIER_REGEX.is_match(&page.title())
+    }
+}
+
+/// A filter keeping only the links in the lead or in certain sections of the article, see [Link::sections]
+///
+/// Sections include their subsections, and headings are compared without case.
+/// Only links with a known placement can be kept, so this filter keeps nothing from [crate::WikipediaBody::Links],
+/// and a page checked without its link with [LinkFilter::allows] is never kept
+#[derive(Clone, Debug, Default)]
+pub struct SectionFilter {
+    lead: bool,
+    sections: Vec<String>,
+}
+
+impl SectionFilter {
+    /// A filter keeping only the links in the lead, including the infobox
+    pub fn lead() -> Self {
+        Self {
+            lead: true,
+            sections: Vec::new(),
+        }
+    }
+
+    /// A filter keeping only the links in sections with the given headings
+    pub fn sections<T: AsRef<str>>(headings: impl IntoIterator<Item = T>) -> Self {
+        Self {
+            lead: false,
+            sections: headings
+                .into_iter()
+                .map(|heading| normalize(heading.as_ref()))
+                .collect(),
+        }
+    }
+
+    /// Sets whether the links in the lead are kept too
+    ///
+    /// The default value is false, unless the filter was created with [SectionFilter::lead]
+    pub fn with_lead(self, lead: bool) -> Self {
+        Self { lead, ..self }
+    }
+
+    /// Sets another section heading to keep the links of
+    pub fn with_section(mut self, heading: impl AsRef<str>) -> Self {
+        self.sections.push(normalize(heading.as_ref()));
+
+        self
+    }
+}
+
+impl LinkFilter for SectionFilter {
+    fn allows(&self, _: &WikipediaPage) -> bool {
+        false
+    }
+
+    fn allows_link(&self, link: &Link) -> bool {
+        match link.placement() {
+            None | Some(LinkPlacement::Navbox) => false,
+            Some(_) if link.sections().is_empty() => self.lead,
+            Some(_) => link
+                .sections()
+                .iter()
+                .any(|heading| self.sections.contains(&normalize(heading))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Namespace;
+
+    fn kept(filter: &dyn LinkFilter, titles: &[&str]) -> Vec<String> {
+        titles
+            .iter()
+            .map(|title| WikipediaPage::from_title(*title))
+            .filter(|page| defaults_allow(filter, page) && filter.allows(page))
+            .map(|page| page.title())
+            .collect()
+    }
+
+    #[test]
+    fn built_in_filters() {
+        let titles = [
+            "Waffle",
+            "1987",
+            "1980s",
+            "300 BC",
+            "20th century",
+            "1987 in film",
+            "March 3",
+            "3 March",
+            "March",
+            "List of Norwegian desserts",
+            "Mercury (disambiguation)",
+            "ISBN (identifier)",
+            "Doi (identifier)",
+            "Wayback Machine",
+            "Category:Desserts",
+        ];
+
+        assert_eq!(
+            kept(&DefaultLinkFilter, &titles).len(),
+            titles.len() - 2 // Wayback Machine and the category
+        );
+        assert_eq!(
+            kept(
+                &DateFilter
+                    .or(ListFilter)
+                    .or(DisambiguationFilter)
+                    .or(IdentifierFilter)
+                    .not(),
+                &titles
+            ),
+            ["Waffle", "March"]
+        );
+        assert_eq!(
+            kept(&TitleFilter::new(["waffle"]).or(DateFilter), &titles[..4]),
+            titles[..4]
+        );
+        assert_eq!(
+            kept(&RegexFilter::new("^[0-9]").unwrap(), &titles[..4]),
+            ["1987", "1980s", "300 BC"]
+        );
+        assert!(RegexFilter::new("(").is_err());
+        assert_eq!(
+            kept(&|_: &WikipediaPage| true, &titles).len(),
+            titles.len() - 2 // Custom filters are composed with the defaults
+        );
+        assert_eq!(
+            kept(
+                &NamespaceFilter::only([Namespace::Category])
+                    .or(|page: &WikipediaPage| page.title() == "Waffle"),
+                &titles
+            ),
+            ["Waffle", "Category:Desserts"]
+        );
+    }
+}
@@ -3,7 +3,7 @@
 /// All languages in the Wikimedia Project's specification
 ///
 /// Generated by 'wikimedia-language-codegen'
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum WikiLanguage {
     Afar,
     Abkhazian,
@@ -831,7 +831,6 @@ impl WikiLanguage {
             WikiLanguage::Quechua => Some("qu"),
             WikiLanguage::Romansh => Some("rm"),
             WikiLanguage::Rundi => Some("rn"),
-            WikiLanguage::Bashkir => Some("ba"),
             WikiLanguage::Romanian => Some("ro"),
             WikiLanguage::Russian => Some("ru"),
             WikiLanguage::Kinyarwanda => Some("rw"),
@@ -840,7 +839,6 @@ impl WikiLanguage {
             WikiLanguage::Sardinian => Some("sc"),
             WikiLanguage::Sicilian => Some("scn"),
             WikiLanguage::Sindhi => Some("sd"),
-            WikiLanguage::NorthernSami => Some("se"),
             WikiLanguage::Sango => Some("sg"),
             WikiLanguage::SerboCroatian => Some("sh"),
             WikiLanguage::Shan => Some("shn"),
@@ -943,6 +941,125 @@ impl WikiLanguage {
     /// Attempts to get the code for the language's 'wikibooks' page. Returns [None] if the language doesn't have a 'wikibooks' page
     pub fn as_code_wikibooks(&self) -> Option<&str> {
         match self {
+            WikiLanguage::Afar => Some("aa"),
+            WikiLanguage::WesternFrisian => Some("fy"),
+            WikiLanguage::Irish => Some("ga"),
+            WikiLanguage::Galician => Some("gl"),
+            WikiLanguage::OldEnglish => Some("ang"),
+            WikiLanguage::Guarani => Some("gn"),
+            WikiLanguage::Gothic => Some("got"),
+            WikiLanguage::Gujarati => Some("gu"),
+            WikiLanguage::Hebrew => Some("he"),
+            WikiLanguage::Hindi => Some("hi"),
+            WikiLanguage::Croatian => Some("hr"),
+            WikiLanguage::Hungarian => Some("hu"),
+            WikiLanguage::Armenian => Some("hy"),
+            WikiLanguage::Interlingua => Some("ia"),
+            WikiLanguage::Indonesian => Some("id"),
+            WikiLanguage::Interlingue => Some("ie"),
+            WikiLanguage::Arabic => Some("ar"),
+            WikiLanguage::Icelandic => Some("is"),
+            WikiLanguage::Italian => Some("it"),
+            WikiLanguage::Japanese => Some("ja"),
+            WikiLanguage::Georgian => Some("ka"),
+            WikiLanguage::Kazakh => Some("kk"),
+            WikiLanguage::Khmer => Some("km"),
+            WikiLanguage::Kannada => Some("kn"),
+            WikiLanguage::Korean => Some("ko"),
+            WikiLanguage::Kashmiri => Some("ks"),
+            WikiLanguage::Kurdish => Some("ku"),
+            WikiLanguage::Kyrgyz => Some("ky"),
+            WikiLanguage::Latin => Some("la"),
+            WikiLanguage::Assamese => Some("as"),
+            WikiLanguage::Luxembourgish => Some("lb"),
+            WikiLanguage::Limburgish => Some("li"),
+            WikiLanguage::Asturian => Some("ast"),
+            WikiLanguage::Lingala => Some("ln"),
+            WikiLanguage::Lithuanian => Some("lt"),
+            WikiLanguage::Latvian => Some("lv"),
+            WikiLanguage::Malagasy => Some("mg"),
+            WikiLanguage::Mori => Some("mi"),
+            WikiLanguage::Macedonian => Some("mk"),
+            WikiLanguage::Malayalam => Some("ml"),
+            WikiLanguage::Mongolian => Some("mn"),
+            WikiLanguage::Marathi => Some("mr"),
+            WikiLanguage::Malay => Some("ms"),
+            WikiLanguage::Burmese => Some("my"),
+            WikiLanguage::Nauru => Some("na"),
+            WikiLanguage::Nahuatl => Some("nah"),
+            WikiLanguage::LowGerman => Some("nds"),
+            WikiLanguage::Nepali => Some("ne"),
+            WikiLanguage::Dutch => Some("nl"),
+            WikiLanguage::Norwegian => Some("no"),
+            WikiLanguage::Aymara => Some("ay"),
+            WikiLanguage::Occitan => Some("oc"),
+            WikiLanguage::Odia => Some("or"),
+            WikiLanguage::Punjabi => Some("pa"),
+            WikiLanguage::Azerbaijani => Some("az"),
+            WikiLanguage::Polish => Some("pl"),
+            WikiLanguage::Pashto => Some("ps"),
+            WikiLanguage::Portuguese => Some("pt"),
+            WikiLanguage::Quechua => Some("qu"),
+            WikiLanguage::Romansh => Some("rm"),
+            WikiLanguage::Bashkir => Some("ba"),
+            WikiLanguage::Romanian => Some("ro"),
+            WikiLanguage::Russian => Some("ru"),
+            WikiLanguage::Sanskrit => Some("sa"),
+            WikiLanguage::NorthernSami => Some("se"),
+            WikiLanguage::Sinhala => Some("si"),
+            WikiLanguage::SimpleEnglish => Some("simple"),
+            WikiLanguage::Slovak => Some("sk"),
+            WikiLanguage::Slovenian => Some("sl"),
+            WikiLanguage::Albanian => Some("sq"),
+            WikiLanguage::Serbian => Some("sr"),
+            WikiLanguage::Sundanese => Some("su"),
+            WikiLanguage::Swedish => Some("sv"),
+            WikiLanguage::Swahili => Some("sw"),
+            WikiLanguage::Tamil => Some("ta"),
+            WikiLanguage::Telugu => Some("te"),
+            WikiLanguage::Tajik => Some("tg"),
+            WikiLanguage::Thai => Some("th"),
+            WikiLanguage::Turkmen => Some("tk"),
+            WikiLanguage::Tagalog => Some("tl"),
+            WikiLanguage::Turkish => Some("tr"),
+            WikiLanguage::Tatar => Some("tt"),
+            WikiLanguage::Uyghur => Some("ug"),
+            WikiLanguage::Belarusian => Some("be"),
+            WikiLanguage::Ukrainian => Some("uk"),
+            WikiLanguage::Urdu => Some("ur"),
+            WikiLanguage::Uzbek => Some("uz"),
+            WikiLanguage::Vietnamese => Some("vi"),
+            WikiLanguage::Volapk => Some("vo"),
+            WikiLanguage::Walloon => Some("wa"),
+            WikiLanguage::Xhosa => Some("xh"),
+            WikiLanguage::Yoruba => Some("yo"),
+            WikiLanguage::Zhuang => Some("za"),
+            WikiLanguage::Chinese => Some("zh"),
+            WikiLanguage::Zulu => Some("zu"),
+            WikiLanguage::Bulgarian => Some("bg"),
+            WikiLanguage::Afrikaans => Some("af"),
+            WikiLanguage::Bislama => Some("bi"),
+            WikiLanguage::Bambara => Some("bm"),
+            WikiLanguage::Bangla => Some("bn"),
+            WikiLanguage::Tibetan => Some("bo"),
+            WikiLanguage::Bosnian => Some("bs"),
+            WikiLanguage::Catalan => Some("ca"),
+            WikiLanguage::Chamorro => Some("ch"),
+            WikiLanguage::Corsican => Some("co"),
+            WikiLanguage::Czech => Some("cs"),
+            WikiLanguage::Chuvash => Some("cv"),
+            WikiLanguage::Welsh => Some("cy"),
+            WikiLanguage::Danish => Some("da"),
+            WikiLanguage::German => Some("de"),
+            WikiLanguage::Greek => Some("el"),
+            WikiLanguage::English => Some("en"),
+            WikiLanguage::Esperanto => Some("eo"),
+            WikiLanguage::Spanish => Some("es"),
+            WikiLanguage::Estonian => Some("et"),
+            WikiLanguage::Basque => Some("eu"),
+            WikiLanguage::Persian => Some("fa"),
+            WikiLanguage::Finnish => Some("fi"),
+            WikiLanguage::French => Some("fr"),
             _ => None,
         }
     }
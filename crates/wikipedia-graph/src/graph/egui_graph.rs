@@ -1,22 +1,43 @@
-use crate::WikipediaPage;
+use crate::{Link, WikiLanguage, WikipediaPage};
 use egui_graphs::Graph;
 
-use super::WikipediaGraph;
+use super::{EdgeWeight, WikipediaGraph};
 
 use petgraph::{
-    Directed,
+    Directed, Direction,
     graph::{IndexType, NodeIndex},
+    visit::EdgeRef,
 };
 
-impl<Index: IndexType> WikipediaGraph<NodeIndex<Index>>
-    for Graph<WikipediaPage, (), Directed, Index>
+impl<Index: IndexType, Edge: EdgeWeight> WikipediaGraph<NodeIndex<Index>>
+    for Graph<WikipediaPage, Edge, Directed, Index>
 {
     fn add_node(&mut self, page: WikipediaPage) -> NodeIndex<Index> {
         self.add_node(page)
     }
 
-    fn add_edge(&mut self, from: NodeIndex<Index>, to: NodeIndex<Index>) {
-        self.add_edge_with_label(from, to, (), String::new());
+    fn add_edge_with_link(
+        &mut self,
+        from: NodeIndex<Index>,
+        to: NodeIndex<Index>,
+        link: Option<&Link>,
+    ) {
+        self.add_edge_with_label(from, to, Edge::from_link(link), String::new());
+    }
+
+    fn add_langlink_edge(
+        &mut self,
+        from: NodeIndex<Index>,
+        to: NodeIndex<Index>,
+        language: WikiLanguage,
+    ) {
+        // The label sets interlanguage edges apart, even when the weight can't
+        self.add_edge_with_label(
+            from,
+            to,
+            Edge::from_langlink(language),
+            language.as_name().to_string(),
+        );
     }
 
     fn node_weight(&self, index: NodeIndex<Index>) -> Option<&WikipediaPage> {
@@ -40,4 +61,34 @@ impl<Index: IndexType> WikipediaGraph<NodeIndex<Index>>
     fn edge_exists(&self, lhs: NodeIndex<Index>, rhs: NodeIndex<Index>) -> bool {
         self.edges_connecting(lhs, rhs).next().is_some()
     }
+
+    fn merge_nodes(&mut self, keep: NodeIndex<Index>, merged: NodeIndex<Index>) {
+        if keep == merged {
+            return;
+        }
+
+        let edges: Vec<(NodeIndex<Index>, NodeIndex<Index>, Edge, String)> =
+            [Direction::Outgoing, Direction::Incoming]
+                .into_iter()
+                .flat_map(|direction| self.edges_directed(merged, direction))
+                .map(|edge| {
+                    let moved = |node| if node == merged { keep } else { node };
+
+                    (
+                        moved(edge.source()),
+                        moved(edge.target()),
+                        edge.weight().payload().clone(),
+                        edge.weight().label(),
+                    )
+                })
+                .collect();
+
+        for (from, to, weight, label) in edges {
+            if from != to && self.edges_connecting(from, to).next().is_none() {
+                self.add_edge_with_label(from, to, weight, label);
+            }
+        }
+
+        self.remove_node(merged);
+    }
 }
@@ -1,4 +1,8 @@
-use crate::WikipediaPage;
+use crate::{DefaultLinkFilter, Link, LinkFilter, WikiLanguage, WikipediaPage};
+#[cfg(feature = "client")]
+use crate::{ResolvedTitles, Title, WikiProject};
+#[cfg(feature = "client")]
+use std::collections::HashMap;
 
 #[cfg(feature = "petgraph")]
 mod petgraph_graph;
@@ -11,6 +15,56 @@ mod egui_graph;
 ///  *This alias requires the `graphs` feature*
 pub type DefaultIndexType = usize;
 
+/// The weight of an edge on a graph, made from the link the edge was created for
+///
+/// Use `()` for edges without a payload, or `Option<Link>` to keep where every link was found
+///
+///  *This trait requires the `graphs` feature*
+pub trait EdgeWeight: Clone {
+    /// Create the weight of an edge, the link is [None] if it isn't known, like for backlinks
+    fn from_link(link: Option<&Link>) -> Self;
+
+    /// Create the weight of an edge between a page and the same page on the wikipedia of another language
+    ///
+    /// By default this is the same as an edge without a known link
+    fn from_langlink(_language: WikiLanguage) -> Self {
+        Self::from_link(None)
+    }
+}
+
+impl EdgeWeight for () {
+    fn from_link(_: Option<&Link>) -> Self {}
+}
+
+impl EdgeWeight for Option<Link> {
+    fn from_link(link: Option<&Link>) -> Self {
+        link.cloned()
+    }
+}
+
+/// An edge weight that tells links between pages apart from interlanguage links
+///
+///  *This enum requires the `graphs` feature*
+#[derive(Clone, Debug)]
+pub enum EdgeKind {
+    /// One page links to the other, with the link if it is known
+    ///
+    /// The link is boxed, since it is far larger than a language
+    Link(Option<Box<Link>>),
+    /// The pages are the same page on the wikipedias of two languages, this is the language of the target
+    Interlanguage(WikiLanguage),
+}
+
+impl EdgeWeight for EdgeKind {
+    fn from_link(link: Option<&Link>) -> Self {
+        Self::Link(link.cloned().map(Box::new))
+    }
+
+    fn from_langlink(language: WikiLanguage) -> Self {
+        Self::Interlanguage(language)
+    }
+}
+
 /// A trait that adds methods for manipulating and expanding wikipedia pages
 ///
 ///  *This trait requires the `graphs` feature*
@@ -20,10 +74,22 @@ pub trait WikipediaGraph<IndexType: Clone> {
     ///  *This method requires the `graphs` feature*
     fn add_node(&mut self, page: WikipediaPage) -> IndexType;
 
+    /// Add an edge to the graph, with a weight made from the link it was created for, see [EdgeWeight]
+    ///
+    ///  *This method requires the `graphs` feature*
+    fn add_edge_with_link(&mut self, from: IndexType, to: IndexType, link: Option<&Link>);
+
+    /// Add an edge from a page to the same page on the wikipedia of another language, see [EdgeWeight::from_langlink]
+    ///
+    ///  *This method requires the `graphs` feature*
+    fn add_langlink_edge(&mut self, from: IndexType, to: IndexType, language: WikiLanguage);
+
     /// Add an edge to the graph
     ///
     ///  *This method requires the `graphs` feature*
-    fn add_edge(&mut self, from: IndexType, to: IndexType);
+    fn add_edge(&mut self, from: IndexType, to: IndexType) {
+        self.add_edge_with_link(from, to, None);
+    }
 
     /// Get the weight of a node on the graph, or None if it doesn't exist
     ///
@@ -50,6 +116,13 @@ pub trait WikipediaGraph<IndexType: Clone> {
     ///  *This method requires the `graphs` feature*
     fn edge_exists(&self, lhs: IndexType, rhs: IndexType) -> bool;
 
+    /// Move the edges of a node onto another node and remove it
+    ///
+    /// Edges that the kept node already has, and edges between the two nodes, are dropped
+    ///
+    ///  *This method requires the `graphs` feature*
+    fn merge_nodes(&mut self, keep: IndexType, merged: IndexType);
+
     /// Get a list of all nodes with their weights and indicies cloned
     ///
     ///  *This method requires the `graphs` feature*
@@ -60,42 +133,172 @@ pub trait WikipediaGraph<IndexType: Clone> {
             .collect()
     }
 
-    /// Place all linked pages as nodes on the graph and return only newly created nodes
+    /// Place all linked pages that [DefaultLinkFilter] keeps as nodes on the graph and return only newly created nodes
     ///
     /// *This method requires the `graphs` feature*
-    #[cfg(feature = "client")]
     fn try_expand_node(&mut self, index: IndexType) -> Option<Vec<IndexType>> {
+        self.try_expand_node_with(index, &DefaultLinkFilter)
+    }
+
+    /// Place all linked pages that a filter keeps as nodes on the graph and return only newly created nodes
+    ///
+    /// The filter is composed with the defaults, see [LinkFilter].
+    /// For example, [crate::NamespaceFilter::only] with [crate::Namespace::Category] builds a graph of categories
+    ///
+    /// *This method requires the `graphs` feature*
+    fn try_expand_node_with(
+        &mut self,
+        index: IndexType,
+        filter: &dyn LinkFilter,
+    ) -> Option<Vec<IndexType>> {
         let page = self.node_weight_mut(index.clone())?.clone();
 
-        let linked_pages = page.try_get_linked_pages()?;
+        let links = page.try_get_links_with(filter)?;
 
-        let mut indicies = Vec::new();
+        Some(self.connect_pages(
+            index,
+            links.iter().map(|link| (link.page().clone(), Some(link))),
+            false,
+            Self::add_edge_with_link,
+        ))
+    }
+
+    /// Place all pages linking to this page as nodes on the graph and return only newly created nodes
+    ///
+    /// The backlinks of the page must be loaded, see [WikipediaPage::load_backlinks]
+    ///
+    /// *This method requires the `graphs` feature*
+    fn try_expand_node_incoming(&mut self, index: IndexType) -> Option<Vec<IndexType>> {
+        let page = self.node_weight_mut(index.clone())?.clone();
+
+        let backlinks = page
+            .try_get_backlinks()?
+            .iter()
+            .map(|page| (page.clone(), None));
+
+        Some(self.connect_pages(index, backlinks, true, Self::add_edge_with_link))
+    }
+
+    /// Connect a node to the same page on the wikipedias of the chosen languages and return only newly created nodes
+    ///
+    /// Every language is connected if none are chosen. The interlanguage links of the page must be loaded, see [WikipediaPage::load_langlinks]
+    ///
+    /// *This method requires the `graphs` feature*
+    fn try_expand_node_languages(
+        &mut self,
+        index: IndexType,
+        languages: &[WikiLanguage],
+    ) -> Option<Vec<IndexType>> {
+        let page = self.node_weight_mut(index.clone())?.clone();
+
+        let langlinks = page
+            .try_get_langlinks()?
+            .iter()
+            .filter(|page| languages.is_empty() || languages.contains(&page.language()))
+            .map(|page| (page.clone(), page.language()));
+
+        Some(self.connect_pages(index, langlinks, false, Self::add_langlink_edge))
+    }
+
+    /// Merge the nodes of pages that resolve to the same canonical title, like `USA` and `United States`
+    ///
+    /// The first node of each title is kept and renamed to the canonical title, and the old titles
+    /// of the kept and merged nodes become its redirects.
+    /// Pages that weren't resolved, or are on another wiki than the titles, are compared by their [Title].
+    /// Pages on different wikis are never merged. Returns the indicies of the removed nodes
+    ///
+    /// *This method requires the `client` and `graphs` features*
+    #[cfg(feature = "client")]
+    fn merge_resolved(&mut self, resolved: &ResolvedTitles) -> Vec<IndexType> {
+        let mut kept: HashMap<(WikiProject, WikiLanguage, Title), IndexType> = HashMap::new();
+        let mut removed = Vec::new();
+
+        for (page, index) in self.node_indicies_owned() {
+            let title = resolved
+                .canonical(&page.title())
+                .filter(|_| {
+                    page.project() == resolved.project() && page.language() == resolved.language()
+                })
+                .unwrap_or(page.canonical_title())
+                .clone();
+
+            let key = (page.project(), page.language(), title);
 
-        for page in linked_pages.into_iter() {
-            match self.node_exists_with_value(&page) {
-                Some(existing_index) => {
-                    if !self.edge_exists(index.clone(), existing_index.clone()) {
-                        self.add_edge(index.clone(), existing_index);
-                    }
+            let Some(keep) = kept.get(&key).cloned() else {
+                if let Some(kept_page) = self.node_weight_mut(index.clone()) {
+                    kept_page.rename_to(&key.2);
+                }
+
+                kept.insert(key, index);
+                continue;
+            };
+
+            if let Some(kept_page) = self.node_weight_mut(keep.clone()) {
+                kept_page.push_redirect(page.title());
+
+                for redirect in page.redirects() {
+                    kept_page.push_redirect(redirect.clone());
                 }
-                None => indicies.push(self.add_node(page)),
             }
+
+            self.merge_nodes(keep, index.clone());
+
+            removed.push(index);
         }
 
-        indicies.iter().for_each(|node_index| {
-            self.add_edge(index.clone(), node_index.clone());
-        });
+        removed
+    }
 
-        Some(indicies)
+    /// Connect pages to a node, adding the pages that aren't on the graph yet and returning their indicies
+    ///
+    /// Edges point from the node to the pages, or from the pages to the node if `incoming` is set.
+    /// Every edge is added by `add_edge` with what its page is paired with, like the link it was made for.
+    /// A page paired more than once only gets an edge for its first pairing
+    ///
+    /// *This method requires the `graphs` feature*
+    fn connect_pages<T>(
+        &mut self,
+        index: IndexType,
+        pages: impl Iterator<Item = (WikipediaPage, T)>,
+        incoming: bool,
+        add_edge: impl Fn(&mut Self, IndexType, IndexType, T),
+    ) -> Vec<IndexType> {
+        let mut indicies = Vec::new();
+
+        for (page, edge) in pages {
+            let page_index = match self.node_exists_with_value(&page) {
+                Some(existing_index) => existing_index,
+                None => {
+                    let new_index = self.add_node(page);
+
+                    indicies.push(new_index.clone());
+
+                    new_index
+                }
+            };
+
+            let (from, to) = match incoming {
+                true => (page_index, index.clone()),
+                false => (index.clone(), page_index),
+            };
+
+            if !self.edge_exists(from.clone(), to.clone()) {
+                add_edge(self, from, to, edge);
+            }
+        }
+
+        indicies
     }
 
     /// Check if a node exists with a specified value
     ///
+    /// Pages that redirected to a node's page are treated as that node, see [WikipediaPage::is_same_page]
+    ///
     ///  *This method requires the `graphs` feature*
     fn node_exists_with_value(&self, page: &WikipediaPage) -> Option<IndexType> {
         self.node_indicies()
             .iter()
-            .find(|(node_page, _)| page.pathinfo() == node_page.pathinfo())
+            .find(|(node_page, _)| node_page.is_same_page(page))
             .map(|(_, index)| index.clone())
     }
 }
@@ -1,19 +1,35 @@
-use crate::WikipediaPage;
+use crate::{Link, WikiLanguage, WikipediaPage};
 
-use super::WikipediaGraph;
+use super::{EdgeWeight, WikipediaGraph};
 
+use petgraph::Direction;
 use petgraph::graph::{IndexType, NodeIndex};
 use petgraph::stable_graph::StableDiGraph;
+use petgraph::visit::EdgeRef;
 
-impl<Index: IndexType> WikipediaGraph<NodeIndex<Index>>
-    for StableDiGraph<WikipediaPage, (), Index>
+impl<Index: IndexType, Edge: EdgeWeight> WikipediaGraph<NodeIndex<Index>>
+    for StableDiGraph<WikipediaPage, Edge, Index>
 {
     fn add_node(&mut self, page: WikipediaPage) -> NodeIndex<Index> {
         self.add_node(page)
     }
 
-    fn add_edge(&mut self, from: NodeIndex<Index>, to: NodeIndex<Index>) {
-        self.add_edge(from, to, ());
+    fn add_edge_with_link(
+        &mut self,
+        from: NodeIndex<Index>,
+        to: NodeIndex<Index>,
+        link: Option<&Link>,
+    ) {
+        self.add_edge(from, to, Edge::from_link(link));
+    }
+
+    fn add_langlink_edge(
+        &mut self,
+        from: NodeIndex<Index>,
+        to: NodeIndex<Index>,
+        language: WikiLanguage,
+    ) {
+        self.add_edge(from, to, Edge::from_langlink(language));
     }
 
     fn node_weight(&self, index: NodeIndex<Index>) -> Option<&WikipediaPage> {
@@ -35,4 +51,33 @@ impl<Index: IndexType> WikipediaGraph<NodeIndex<Index>>
     fn edge_exists(&self, lhs: NodeIndex<Index>, rhs: NodeIndex<Index>) -> bool {
         self.contains_edge(lhs, rhs)
     }
+
+    fn merge_nodes(&mut self, keep: NodeIndex<Index>, merged: NodeIndex<Index>) {
+        if keep == merged {
+            return;
+        }
+
+        let edges: Vec<(NodeIndex<Index>, NodeIndex<Index>, Edge)> =
+            [Direction::Outgoing, Direction::Incoming]
+                .into_iter()
+                .flat_map(|direction| self.edges_directed(merged, direction))
+                .map(|edge| {
+                    let moved = |node| if node == merged { keep } else { node };
+
+                    (
+                        moved(edge.source()),
+                        moved(edge.target()),
+                        edge.weight().clone(),
+                    )
+                })
+                .collect();
+
+        for (from, to, weight) in edges {
+            if from != to && !self.contains_edge(from, to) {
+                self.add_edge(from, to, weight);
+            }
+        }
+
+        self.remove_node(merged);
+    }
 }
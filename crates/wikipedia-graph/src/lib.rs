@@ -6,14 +6,15 @@
 //! A tool compatible with your favorite graphing crates to make graphing Wikipedia a walk in the forest
 //!
 //! - A versatile struct for managing Wikipedia pages
-//! - A configurable client
+//! - A configurable client, with callback, async and blocking (`blocking` feature) APIs
 //! - Complete WASM support (theoretically)
 //!
 //! # Example
 //! ```no_run
+//! # #[cfg(feature = "client")]
+//! # fn main() -> Result< (), Box<dyn std::error::Error>> {
 //! # use wikipedia_graph::{HttpError, WikipediaPage, WikipediaClient};
 //! # use std::sync::mpsc::*;
-//! # fn main() -> Result< (), Box<dyn std::error::Error>> {
 //! let mut page = WikipediaPage::from_title("Waffle");
 //! let client = WikipediaClient::default();
 //!
@@ -30,18 +31,44 @@
 //! }
 //! # Ok(())
 //! # }
+//! # #[cfg(not(feature = "client"))]
+//! # fn main() {}
+//! ```
+//!
+//! Every method that takes a callback also has an async counterpart, which works natively and on wasm32
+//!
+//! ```no_run
+//! # #[cfg(feature = "client")]
+//! # fn main() -> Result< (), Box<dyn std::error::Error>> {
+//! # use wikipedia_graph::{WikipediaPage, WikipediaClient};
+//! let client = WikipediaClient::default();
+//!
+//! let page = pollster::block_on(WikipediaPage::from_title("Waffle").load_page_text_async(&client))?;
+//!
+//! println!("Page title: {}", page.title());
+//! # Ok(())
+//! # }
+//! # #[cfg(not(feature = "client"))]
+//! # fn main() {}
 //! ```
 
+mod filter;
+mod link;
+mod namespace;
 mod page;
+mod project;
+mod response;
+mod summary;
+mod title;
+mod wikitext;
 mod wikimedia_languages {
-    #![allow(missing_docs)]
+    #![allow(missing_docs, clippy::match_single_binding)]
     include!("generated/wikimedia_languages.rs");
 }
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "client")] {
         mod client;
-        mod graph;
 
         pub use client::HeaderError;
 
@@ -49,16 +76,31 @@ cfg_if::cfg_if! {
 
         pub use client::WikipediaClient;
 
-        pub use client::WikipediaClientConfig;
+        pub use client::{WikipediaClientConfig, BackoffPolicy, RateLimit, CLIENT_REDIRECTS, MAX_BATCH_TITLES};
 
         pub use client::HttpError;
+
+        pub use client::{HttpTransport, TransportCallback, EhttpTransport, FixtureTransport};
+
+        pub use client::{PageCache, CacheKey, MemoryCache};
+
+        pub use client::RefreshReport;
+
+        pub use client::ResolvedTitles;
+
+        #[cfg(not(target_arch = "wasm32"))]
+        pub use client::DirectoryCache;
+
+        #[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+        pub use client::BlockingWikipediaClient;
     }
 }
 
 cfg_if::cfg_if! {
     if #[cfg(feature = "graphs")] {
+        mod graph;
 
-        pub use graph::{WikipediaGraph, DefaultIndexType};
+        pub use graph::{WikipediaGraph, EdgeKind, EdgeWeight, DefaultIndexType};
     }
 }
 
@@ -68,4 +110,26 @@ pub use url::Url;
 
 pub use wikimedia_languages::WikiLanguage;
 
-pub use page::WikipediaBody;
+pub use project::WikiProject;
+
+pub use page::{WikipediaBody, WikipediaUrlType};
+
+pub use response::{
+    ApiErrorBody, ApiWarning, ApiWarnings, LangLink, PageRef, ParseResponse, ParsedPage, Query,
+    QueryPage, QueryResponse, ResponseError, Thumbnail, TitleMapping, Wikitext,
+};
+
+pub use summary::PageSummary;
+
+pub use title::Title;
+
+pub use namespace::{Namespace, NamespaceFilter};
+
+pub use filter::{
+    And, DateFilter, DefaultLinkFilter, DisambiguationFilter, IdentifierFilter,
+    InvalidPatternError, LinkFilter, ListFilter, Not, Or, RegexFilter, SectionFilter, TitleFilter,
+};
+
+pub use wikitext::WikiLink;
+
+pub use link::{Link, LinkPlacement};
@@ -0,0 +1,313 @@
+use crate::wikitext::WikiLink;
+use crate::{Namespace, QueryResponse, WikiLanguage, WikiProject, WikipediaPage};
+use regex::Regex;
+
+/// The part of an article a [Link] sits in
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LinkPlacement {
+    /// The introduction, before the first heading
+    Lead,
+    /// The article after the first heading
+    Body,
+    /// The box summarizing the article, usually at the top right
+    Infobox,
+    /// The boxes linking to related articles, usually at the bottom
+    Navbox,
+}
+
+/// A link from one page to another, along with where it was found in the article
+///
+/// Edges of a [crate::WikipediaGraph] can carry links, see [crate::EdgeWeight]
+#[derive(Clone, Debug)]
+pub struct Link {
+    page: WikipediaPage,
+    display: Option<String>,
+    anchor: Option<String>,
+    sections: Vec<String>,
+    position: usize,
+    placement: Option<LinkPlacement>,
+}
+
+impl Link {
+    /// The page the link leads to
+    pub fn page(&self) -> &WikipediaPage {
+        &self.page
+    }
+
+    /// The text the link is shown as, if the body has it, like `Cloudberries` in `[[Rubus chamaemorus|Cloudberries]]`
+    pub fn display(&self) -> Option<&str> {
+        self.display.as_deref()
+    }
+
+    /// The section of the page the link leads to, like `History` in `Waffle#History`
+    pub fn anchor(&self) -> Option<&str> {
+        self.anchor.as_deref()
+    }
+
+    /// The heading of the section the link sits in, or [None] in the lead
+    pub fn section(&self) -> Option<&str> {
+        self.sections.last().map(String::as_str)
+    }
+
+    /// The headings of the section the link sits in and the sections it is part of, outermost first
+    ///
+    /// For example `["History", "Origins"]` for a link under `=== Origins ===` in `== History ==`
+    pub fn sections(&self) -> &[String] {
+        &self.sections
+    }
+
+    /// The index of the link among every link in the article, in the order they appear
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// The part of the article the link sits in, or [None] if the body doesn't tell, like for [crate::WikipediaBody::Links]
+    pub fn placement(&self) -> Option<LinkPlacement> {
+        self.placement
+    }
+
+    /// Move the page the link leads to onto the wiki of the page it was found on
+    ///
+    /// Interwiki links to a language, like `fr:Gaufre`, move to the wiki in that language instead.
+    /// Returns [None] for interwiki links to other projects, like `wikt:waffle`
+    pub(crate) fn on_wiki(self, project: WikiProject, language: WikiLanguage) -> Option<Self> {
+        let page = match self.page.namespace() {
+            Namespace::Interwiki(prefix) => {
+                let language = WikiLanguage::from_code(&prefix)?;
+
+                let title = self.page.title();
+
+                let (_, name) = title.split_once(':')?;
+
+                WikipediaPage::from_title(name.trim())
+                    .with_project(project)
+                    .with_language(language)
+            }
+            _ => self.page.with_project(project).with_language(language),
+        };
+
+        Some(Self { page, ..self })
+    }
+}
+
+/// Get the links in wikitext
+pub(crate) fn links_from_wikitext(wikitext: &str) -> Vec<Link> {
+    WikiLink::parse_all_located(wikitext)
+        .into_iter()
+        .enumerate()
+        .map(|(position, located)| Link {
+            page: WikipediaPage::from_title(located.link.target()),
+            display: located.link.display().map(ToString::to_string),
+            anchor: located.link.anchor().map(ToString::to_string),
+            sections: located.sections,
+            position,
+            placement: Some(located.placement),
+        })
+        .collect()
+}
+
+/// Get the links of a links response, which only carries their titles
+pub(crate) fn links_from_query(value: &QueryResponse) -> Result<Vec<Link>, crate::ResponseError> {
+    Ok(value
+        .first_page()?
+        .links
+        .iter()
+        .enumerate()
+        .map(|(position, link)| Link {
+            page: WikipediaPage::from_title(link.title.as_str()).with_namespace_id(link.ns),
+            display: None,
+            anchor: None,
+            sections: Vec::new(),
+            position,
+            placement: None,
+        })
+        .collect())
+}
+
+/// A regex that gets the pathinfo, anchor and text of every link to a page of the wiki
+const HTML_ANCHOR_REGEX: &lazy_regex::Lazy<Regex> =
+    lazy_regex::regex!(r#"(?s)<a\s[^>]*?href="/wiki/([^"\#?]+)(?:\#([^"]*))?"[^>]*>(.*?)</a>"#);
+
+/// A regex that gets the level and text of every heading
+const HTML_HEADING_REGEX: &lazy_regex::Lazy<Regex> =
+    lazy_regex::regex!(r#"(?s)<h([2-6])[^>]*>(.*?)</h[2-6]>"#);
+
+/// A regex that finds the start of every infobox and navbox, and the name of its tag
+const HTML_BOX_REGEX: &lazy_regex::Lazy<Regex> =
+    lazy_regex::regex!(r#"<(table|div)\s[^>]*?class="[^"]*?\b(infobox|navbox)\b"#);
+
+/// A regex that finds every opening and closing table and div tag
+const HTML_BLOCK_TAG_REGEX: &lazy_regex::Lazy<Regex> = lazy_regex::regex!(r#"<(/?)(table|div)\b"#);
+
+/// A regex that finds every tag, to get the text around them
+const HTML_TAG_REGEX: &lazy_regex::Lazy<Regex> = lazy_regex::regex!(r#"<[^>]*>"#);
+
+/// Remove the tags from HTML, leaving only its text
+fn html_text(html: &str) -> String {
+    HTML_TAG_REGEX.replace_all(html, "").trim().to_string()
+}
+
+/// Find the end of the element starting at `start`, counting nested elements with the same tag
+fn element_end(html: &str, start: usize, tag: &str) -> usize {
+    let mut depth = 0;
+
+    for capture in HTML_BLOCK_TAG_REGEX.captures_iter(&html[start..]) {
+        if &capture[2] != tag {
+            continue;
+        }
+
+        match &capture[1] {
+            "/" => depth -= 1,
+            _ => depth += 1,
+        }
+
+        if depth == 0 {
+            return start + capture.get(0).map_or(0, |tag| tag.end());
+        }
+    }
+
+    html.len()
+}
+
+/// Get the links in the article content of a page's HTML
+pub(crate) fn links_from_html(content: &str) -> Vec<Link> {
+    // The sections every heading starts, with the offset of the heading
+    let mut sections: Vec<(usize, Vec<(usize, String)>)> = Vec::new();
+
+    for capture in HTML_HEADING_REGEX.captures_iter(content) {
+        let level: usize = capture[1].parse().unwrap_or(2);
+
+        let title = html_text(&capture[2]);
+
+        let title = title.strip_suffix("[edit]").unwrap_or(&title).trim();
+
+        let mut path = sections
+            .last()
+            .map(|(_, path)| path.clone())
+            .unwrap_or_default();
+
+        while path.last().is_some_and(|(parent, _)| *parent >= level) {
+            path.pop();
+        }
+
+        path.push((level, title.to_string()));
+
+        sections.push((capture.get(0).map_or(0, |heading| heading.start()), path));
+    }
+
+    let boxes: Vec<(usize, usize, LinkPlacement)> = HTML_BOX_REGEX
+        .captures_iter(content)
+        .filter_map(|capture| {
+            let start = capture.get(0)?.start();
+
+            let placement = match &capture[2] {
+                "infobox" => LinkPlacement::Infobox,
+                _ => LinkPlacement::Navbox,
+            };
+
+            Some((start, element_end(content, start, &capture[1]), placement))
+        })
+        .collect();
+
+    HTML_ANCHOR_REGEX
+        .captures_iter(content)
+        .enumerate()
+        .filter_map(|(position, capture)| {
+            let offset = capture.get(0)?.start();
+
+            let sections: Vec<String> = sections
+                .iter()
+                .take_while(|(start, _)| *start < offset)
+                .last()
+                .map(|(_, path)| path.iter().map(|(_, title)| title.clone()).collect())
+                .unwrap_or_default();
+
+            let placement = boxes
+                .iter()
+                .find(|(start, end, _)| (*start..*end).contains(&offset))
+                .map(|(_, _, placement)| *placement)
+                .unwrap_or(match sections.is_empty() {
+                    true => LinkPlacement::Lead,
+                    false => LinkPlacement::Body,
+                });
+
+            let display = html_text(&capture[3]);
+
+            Some(Link {
+                page: WikipediaPage::from_title(&capture[1]),
+                display: (!display.is_empty()).then_some(display),
+                anchor: capture
+                    .get(2)
+                    .map(|anchor| crate::title::percent_decode(&anchor.as_str().replace('_', " "))),
+                sections,
+                position,
+                placement: Some(placement),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn html_link_locations() {
+        let content = r#"<div id="mw-content-text"><table class="infobox hrecipe"><tr><td><a href="/wiki/Norway">Norway</a>
+<table><tr><td><a href="/wiki/Dessert">Course</a></td></tr></table></td></tr></table>
+<p><b>Multekrem</b> is made with <a href="/wiki/Rubus_chamaemorus" title="Cloudberry">cloudberries</a></p>
+<div class="mw-heading mw-heading2"><h2 id="Serving">Serving</h2></div>
+<h3><span class="mw-headline" id="Toppings">Toppings</span><span class="mw-editsection">[<a href="/w/index.php?action=edit">edit</a>]</span></h3>
+<p>With <a href="/wiki/Waffle#History"><i>waffles</i></a></p>
+<div role="navigation" class="navbox"><div><a href="/wiki/Riskrem">Riskrem</a></div></div>
+<a href="/wiki/Kransekake">After the navbox</a></div>"#;
+
+        let links: Vec<(String, Option<String>, Option<LinkPlacement>)> = links_from_html(content)
+            .into_iter()
+            .map(|link| {
+                (
+                    link.page.title(),
+                    link.section().map(ToString::to_string),
+                    link.placement,
+                )
+            })
+            .collect();
+
+        let toppings = Some("Toppings".to_string());
+
+        assert_eq!(
+            links,
+            [
+                ("Norway".to_string(), None, Some(LinkPlacement::Infobox)),
+                ("Dessert".to_string(), None, Some(LinkPlacement::Infobox)),
+                (
+                    "Rubus chamaemorus".to_string(),
+                    None,
+                    Some(LinkPlacement::Lead)
+                ),
+                (
+                    "Waffle".to_string(),
+                    toppings.clone(),
+                    Some(LinkPlacement::Body)
+                ),
+                (
+                    "Riskrem".to_string(),
+                    toppings.clone(),
+                    Some(LinkPlacement::Navbox)
+                ),
+                (
+                    "Kransekake".to_string(),
+                    toppings,
+                    Some(LinkPlacement::Body)
+                ),
+            ]
+        );
+
+        let waffle = &links_from_html(content)[3];
+
+        assert_eq!(waffle.sections(), ["Serving", "Toppings"]);
+        assert_eq!(waffle.anchor(), Some("History"));
+        assert_eq!(waffle.display(), Some("waffles"));
+        assert_eq!(waffle.position(), 3);
+    }
+}
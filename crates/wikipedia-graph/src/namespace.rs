@@ -0,0 +1,309 @@
+use crate::WikiLanguage;
+use std::fmt::Display;
+
+/// The names of the namespaces on Wikipedia with their IDs, including aliases like `Image` for `File`
+///
+/// The first name of every ID is its canonical name. Only the English names are known,
+/// wikis in other languages also accept these but mostly use their own, like `Kategorie` on the German Wikipedia
+const NAMESPACE_NAMES: [(&str, i64); 31] = [
+    ("Media", -2),
+    ("Special", -1),
+    ("Talk", 1),
+    ("User", 2),
+    ("User talk", 3),
+    ("Wikipedia", 4),
+    ("Project", 4),
+    ("WP", 4),
+    ("Wikipedia talk", 5),
+    ("Project talk", 5),
+    ("WT", 5),
+    ("File", 6),
+    ("Image", 6),
+    ("File talk", 7),
+    ("Image talk", 7),
+    ("MediaWiki", 8),
+    ("MediaWiki talk", 9),
+    ("Template", 10),
+    ("Template talk", 11),
+    ("Help", 12),
+    ("Help talk", 13),
+    ("Category", 14),
+    ("Category talk", 15),
+    ("Portal", 100),
+    ("Portal talk", 101),
+    ("Draft", 118),
+    ("Draft talk", 119),
+    ("TimedText", 710),
+    ("TimedText talk", 711),
+    ("Module", 828),
+    ("Module talk", 829),
+];
+
+/// Prefixes that link to other wikimedia projects instead of a namespace
+const PROJECT_PREFIXES: [&str; 13] = [
+    "wiktionary",
+    "wikt",
+    "wikibooks",
+    "wikiquote",
+    "wikisource",
+    "wikinews",
+    "wikiversity",
+    "wikivoyage",
+    "wikidata",
+    "commons",
+    "meta",
+    "species",
+    "mw",
+];
+
+/// The namespace of a page, which is the prefix of its title like `Category:` in `Category:Desserts`
+///
+/// Articles are in [Namespace::Main], which has no prefix
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Namespace {
+    /// Articles, like `Waffle`
+    Main,
+    /// Talk pages of articles, like `Talk:Waffle`
+    Talk,
+    /// User pages, like `User:Jimbo Wales`
+    User,
+    /// Pages about Wikipedia itself, like `Wikipedia:About`
+    Project,
+    /// Images, sounds and other files, like `File:Waffles.jpg`
+    File,
+    /// Templates, like `Template:Infobox food`
+    Template,
+    /// Help pages, like `Help:Wikitext`
+    Help,
+    /// Categories, like `Category:Desserts`
+    Category,
+    /// Portals, like `Portal:Food`
+    Portal,
+    /// Pages generated by the software, like `Special:Random`
+    Special,
+    /// Any other namespace by its ID, like the talk pages of other namespaces
+    Other(i64),
+    /// A page on another wiki, like `fr:Gaufre` or `wikt:waffle`, stored as the lowercase prefix
+    Interwiki(String),
+}
+
+impl Namespace {
+    /// Get the namespace with a certain ID, as used in the `ns` field of API responses
+    pub fn from_id(id: i64) -> Self {
+        match id {
+            0 => Self::Main,
+            1 => Self::Talk,
+            2 => Self::User,
+            4 => Self::Project,
+            6 => Self::File,
+            10 => Self::Template,
+            12 => Self::Help,
+            14 => Self::Category,
+            100 => Self::Portal,
+            -1 => Self::Special,
+            id => Self::Other(id),
+        }
+    }
+
+    /// Get the ID of the namespace, or [None] for [Namespace::Interwiki]
+    pub fn id(&self) -> Option<i64> {
+        match self {
+            Self::Main => Some(0),
+            Self::Talk => Some(1),
+            Self::User => Some(2),
+            Self::Project => Some(4),
+            Self::File => Some(6),
+            Self::Template => Some(10),
+            Self::Help => Some(12),
+            Self::Category => Some(14),
+            Self::Portal => Some(100),
+            Self::Special => Some(-1),
+            Self::Other(id) => Some(*id),
+            Self::Interwiki(_) => None,
+        }
+    }
+
+    /// Get the namespace of a title prefix, like `Category` or `fr`, or [None] if it isn't one
+    ///
+    /// Prefixes are matched without case and with underscores treated as spaces
+    pub fn from_prefix(prefix: &str) -> Option<Self> {
+        let prefix = prefix.trim().replace('_', " ");
+
+        if let Some((_, id)) = NAMESPACE_NAMES
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(&prefix))
+        {
+            return Some(Self::from_id(*id));
+        }
+
+        let prefix = prefix.to_lowercase();
+
+        if PROJECT_PREFIXES.contains(&prefix.as_str())
+            || WikiLanguage::from_code(&prefix).is_some_and(|lang| lang.as_code_wiki().is_some())
+        {
+            return Some(Self::Interwiki(prefix));
+        }
+
+        None
+    }
+
+    /// Get the namespace of a title
+    ///
+    /// Titles without a known prefix are articles, so `Star Wars: Andor` is in [Namespace::Main]
+    pub fn of_title(title: &str) -> Self {
+        title
+            .split_once(':')
+            .and_then(|(prefix, _)| Self::from_prefix(prefix))
+            .unwrap_or(Self::Main)
+    }
+
+    /// The canonical prefix of the namespace, which is empty for [Namespace::Main]
+    ///
+    /// Namespaces without a known name are written as their ID, like `3000` for `Other(3000)`
+    pub fn prefix(&self) -> String {
+        match self {
+            Self::Main => String::new(),
+            Self::Interwiki(prefix) => prefix.clone(),
+            namespace => namespace.id().map_or_else(String::new, |id| {
+                NAMESPACE_NAMES
+                    .iter()
+                    .find(|(_, known)| *known == id)
+                    .map_or_else(|| id.to_string(), |(name, _)| name.to_string())
+            }),
+        }
+    }
+
+    /// Check if the namespace holds articles
+    pub fn is_main(&self) -> bool {
+        *self == Self::Main
+    }
+}
+
+impl Display for Namespace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Main => write!(f, "(Main)"),
+            namespace => write!(f, "{}", namespace.prefix()),
+        }
+    }
+}
+
+/// Decides which namespaces linked pages may be in
+///
+/// The default only allows articles, but other namespaces can be allowed to build a graph of categories for example
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NamespaceFilter {
+    namespaces: Vec<Namespace>,
+    any_namespace: bool,
+    interwiki: bool,
+}
+
+impl Default for NamespaceFilter {
+    fn default() -> Self {
+        Self::articles()
+    }
+}
+
+impl NamespaceFilter {
+    /// A filter that only allows articles
+    pub fn articles() -> Self {
+        Self::only([Namespace::Main])
+    }
+
+    /// A filter that allows every namespace of the wiki, but not pages on other wikis
+    pub fn all() -> Self {
+        Self {
+            namespaces: Vec::new(),
+            any_namespace: true,
+            interwiki: false,
+        }
+    }
+
+    /// A filter that only allows the given namespaces
+    pub fn only(namespaces: impl IntoIterator<Item = Namespace>) -> Self {
+        Self {
+            namespaces: namespaces.into_iter().collect(),
+            any_namespace: false,
+            interwiki: false,
+        }
+    }
+
+    /// Sets another namespace to allow
+    pub fn with(mut self, namespace: Namespace) -> Self {
+        if !self.namespaces.contains(&namespace) {
+            self.namespaces.push(namespace);
+        }
+
+        self
+    }
+
+    /// Sets whether pages on other wikis, like `fr:Gaufre`, are allowed
+    ///
+    /// The default value is false
+    pub fn with_interwiki(self, interwiki: bool) -> Self {
+        Self { interwiki, ..self }
+    }
+
+    /// Check if a namespace is allowed
+    pub fn allows(&self, namespace: &Namespace) -> bool {
+        match namespace {
+            Namespace::Interwiki(_) => self.interwiki || self.namespaces.contains(namespace),
+            namespace => self.any_namespace || self.namespaces.contains(namespace),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn title_namespaces() {
+        assert_eq!(Namespace::of_title("Waffle"), Namespace::Main);
+        assert_eq!(Namespace::of_title("Star Wars: Andor"), Namespace::Main);
+        assert_eq!(
+            Namespace::of_title("category:Desserts"),
+            Namespace::Category
+        );
+        assert_eq!(Namespace::of_title("Image:Waffles.jpg"), Namespace::File);
+        assert_eq!(
+            Namespace::of_title("Category_talk:Desserts"),
+            Namespace::Other(15)
+        );
+        assert_eq!(
+            Namespace::of_title("FR:Gaufre"),
+            Namespace::Interwiki("fr".to_string())
+        );
+        assert_eq!(
+            Namespace::of_title("wikt:waffle"),
+            Namespace::Interwiki("wikt".to_string())
+        );
+
+        assert_eq!(Namespace::from_id(14).prefix(), "Category");
+        assert_eq!(Namespace::Other(15).prefix(), "Category talk");
+        assert_eq!(Namespace::Project.prefix(), "Wikipedia");
+        assert_eq!(Namespace::Other(3000).prefix(), "3000");
+    }
+
+    #[test]
+    fn filters() {
+        let articles = NamespaceFilter::default();
+
+        assert!(articles.allows(&Namespace::Main));
+        assert!(!articles.allows(&Namespace::Category));
+
+        let categories = NamespaceFilter::only([Namespace::Category]).with(Namespace::Main);
+
+        assert!(categories.allows(&Namespace::Category));
+        assert!(!categories.allows(&Namespace::File));
+
+        let all = NamespaceFilter::all();
+
+        assert!(all.allows(&Namespace::Other(829)));
+        assert!(!all.allows(&Namespace::Interwiki("fr".to_string())));
+        assert!(
+            all.with_interwiki(true)
+                .allows(&Namespace::Interwiki("fr".to_string()))
+        );
+    }
+}
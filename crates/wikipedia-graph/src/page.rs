@@ -1,37 +1,78 @@
+use crate::filter::{self, DefaultLinkFilter, LinkFilter};
+use crate::link::{self, Link};
+use crate::namespace::Namespace;
+use crate::project::WikiProject;
+use crate::response::{self, ParseResponse, QueryResponse, ResponseError};
+use crate::summary::PageSummary;
+use crate::title::Title;
 use crate::wikimedia_languages::WikiLanguage;
+use crate::wikitext::WikiLink;
 use itertools::Itertools;
 use regex::Regex;
-use serde_json::Value;
+use serde::{Deserialize, Serialize};
+use std::hash::{Hash, Hasher};
 use thiserror::Error;
 use url::Url;
 
 #[cfg(feature = "client")]
-use crate::client::{HttpError, WikipediaClient};
+use crate::client::{HttpError, LoadedBody, WikipediaClient};
+#[cfg(feature = "client")]
+use std::future::Future;
 
 /// A struct representing the location of a Wikipedia page and its body
+///
+/// Pages are equal if their titles, see [Title], languages and projects are, what was loaded into them doesn't matter
 #[derive(Clone, Debug)]
 pub struct WikipediaPage {
     // This is called 'pathinfo' it's the part of the url after the /
     pathinfo: String,
+    title: Title,
+    /// The namespace the API sent for the page, if it came from a response
+    namespace: Option<Namespace>,
+    language: WikiLanguage,
+    project: WikiProject,
     body: Option<WikipediaBody>,
+    redirects: Vec<String>,
+    backlinks: Option<Vec<WikipediaPage>>,
+    langlinks: Option<Vec<WikipediaPage>>,
+    etag: Option<String>,
+    summary: Option<PageSummary>,
+}
+
+impl PartialEq for WikipediaPage {
+    fn eq(&self, other: &Self) -> bool {
+        self.title == other.title
+            && self.language == other.language
+            && self.project == other.project
+    }
+}
+
+impl Eq for WikipediaPage {}
+
+impl Hash for WikipediaPage {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.title.hash(state);
+        self.language.hash(state);
+        self.project.hash(state);
+    }
 }
 
 /// An error that may occur when a language has no iso 639-1 representation
 #[derive(Error, Debug, Clone)]
-#[error("Language has no valid representation on wikipedia")]
+#[error("Language has no valid representation on the wiki project")]
 pub struct WikipediaLanguageInvalidError;
 
-/// An error that may occur when the pathinfo of a page cannot be seperated from its body
-#[derive(Debug, Error)]
-#[error("Failed to parse pathinfo from body")]
-pub struct PathinfoParseError;
-
 /// An error that may occur when parsing directly from a wikipedia URL
 #[derive(Debug, Error)]
 pub enum WikipediaUrlError {
-    /// The host of the URL does not lead to wikipedia.org
-    #[error("URL host is not the wikipedia domain")]
+    /// The host of the URL does not lead to the domain of a [WikiProject], like wikipedia.org
+    #[error("URL host is not the domain of a wiki project")]
     InvalidHost,
+    /// The subdomain of the URL is not the code of a language with an edition of the project
+    ///
+    /// For example: 'xx.wikipedia.org/wiki/Waffle'
+    #[error("URL subdomain '{0}' is not a language of the wiki project")]
+    InvalidLanguage(String),
     /// The path of the URL does not lead to a wiki
     ///
     /// For example: 'wikipedia.org/wiki/Waffle vs. wikipedia.org/Waffle (I don't know if this actually happens)
@@ -43,74 +84,75 @@ pub enum WikipediaUrlError {
     InvalidURL(#[from] url::ParseError),
 }
 
-/// The body of a Wikipedia page. The two current supported formats are the wikitext and links, both stored as JSON values.
-#[derive(Clone, Debug)]
+/// The body of a Wikipedia page. The supported formats are the wikitext and links, both stored as typed API responses, and the HTML article.
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum WikipediaBody {
     /// The (wikitext)[https://en.wikipedia.org/wiki/Help:Wikitext] of a page, stored in a thin layer of JSON
     ///
-    /// The wikitext JSON comes from this api call: <https://en.wikipedia.org/w/api.php?origin=*&action=parse&prop=wikitext&format=json&page=Waffle>
-    WikiText(serde_json::Value),
+    /// The wikitext JSON comes from this api call: <https://en.wikipedia.org/w/api.php?origin=*&action=parse&prop=wikitext|revid&format=json&page=Waffle>
+    WikiText(ParseResponse),
     /// The links of a page, stored in a thin layer of JSON
     ///
-    /// The links JSON comes from this api call: <>
-    Links(serde_json::Value),
+    /// The links JSON comes from this api call: <https://en.wikipedia.org/w/api.php?action=query&format=json&prop=links|info&pllimit=500&origin=*&titles=Waffle>
+    Links(QueryResponse),
+    /// The HTML of a page, as it is shown on <https://en.wikipedia.org/wiki/Waffle>
+    Html(String),
 }
 
-impl WikipediaBody {
-    /// A list of page titles that won't be included in linked pages
-    const FILTERED_PAGES: [&str; 1] = [
-        "Wayback Machine", // Almost all sources are linked to through the wayback machine
-    ];
+impl std::fmt::Display for WikipediaBody {
+    /// Write the body of the page
+    ///
+    /// Output is either JSON or HTML
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let json = match self {
+            Self::WikiText(t) => serde_json::to_string(t),
+            Self::Links(t) => serde_json::to_string(t),
+            Self::Html(t) => return write!(f, "{t}"),
+        };
 
-    /// A list of file endings to remove file wikilinks from the parsing results
-    const FILE_ENDINGS: [&str; 3] = [".png", ".svg", ".jpg"];
+        write!(f, "{}", json.map_err(|_| std::fmt::Error)?)
+    }
+}
 
-    /// A regex that gets all of the wikilinks from a page
-    const WIKITEXT_LINK_REGEX: &lazy_regex::Lazy<Regex> = lazy_regex::regex!(
-        r#"\[\[(?:[^[\[\]\|\#:]]+: ?)?([^(?:[\[\]\|\#:]]+)(?:[\|\#][[^[\[\]]]+]+)?\]\]"#
-    );
+impl WikipediaBody {
+    /// A regex that gets the pathinfo of a page from the canonical link in its HTML
+    const HTML_CANONICAL_REGEX: &lazy_regex::Lazy<Regex> =
+        lazy_regex::regex!(r#"<link rel="canonical" href="[^"]*/wiki/([^"]+)""#);
 
-    // This regex first detects if the text is between two brackets
-    // Then it checks for a : and ignores everything before that
-    // Then it matches for all characters except brackets and |
-    // Then it checks for a |, and if it finds one, ignores the content behind it until the closing brackets
+    /// The start of the article content in the HTML of a page, links before it are part of the site's navigation
+    const HTML_CONTENT_START: &str = r#"id="mw-content-text""#;
 
-    /// Serialize the JSON from a wikitext response and wrap it
-    pub fn wikitext_from_text(text: &str) -> Result<WikipediaBody, serde_json::Error> {
-        serde_json::from_str(text).map(|val| WikipediaBody::WikiText(val))
-    }
+    /// A regex that gets the revision ID from the page configuration in the HTML of a page
+    const HTML_REVISION_REGEX: &lazy_regex::Lazy<Regex> =
+        lazy_regex::regex!(r#""wgRevisionId":(\d+)"#);
 
-    /// Serialize the JSON from a links response and wrap it
-    pub fn links_from_text(text: &str) -> Result<WikipediaBody, serde_json::Error> {
-        serde_json::from_str(text).map(|val| WikipediaBody::Links(val))
+    /// Deserialize the JSON from a wikitext response and wrap it
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the response is an API error or isn't a parse response
+    pub fn wikitext_from_text(text: &str) -> Result<WikipediaBody, ResponseError> {
+        response::parse(text).map(WikipediaBody::WikiText)
     }
 
-    /// Get the body of the page
+    /// Deserialize the JSON from a links response and wrap it
     ///
-    /// Output is either JSON or HTML
-    pub fn to_string(self) -> String {
-        match self {
-            Self::WikiText(t) => t.to_string(),
-            Self::Links(t) => t.to_string(),
-        }
+    /// # Errors
+    ///
+    /// This method fails if the response is an API error or isn't a query response
+    pub fn links_from_text(text: &str) -> Result<WikipediaBody, ResponseError> {
+        response::parse(text).map(WikipediaBody::Links)
     }
 
     /// Checks if the body is a redirect, if so returns a new page with the correct pathinfo
     pub fn redirects_to(&self) -> Option<WikipediaPage> {
         match self {
             Self::Links(_) => None, // The API call doesn't actually tell you
-            Self::WikiText(t) => {
-                match t
-                    .get("parse")
-                    .and_then(|parse| parse.get("wikitext"))
-                    .and_then(|wikitext| wikitext.as_object()?.iter().next()?.1.as_str())
-                {
-                    Some(t) if t.contains("#REDIRECT") => {
-                        self.get_linked_pages().and_then(|mut pages| pages.next())
-                    }
-                    _ => None,
-                }
+            Self::Html(_) => None,  // Wikipedia follows redirects itself when serving HTML
+            Self::WikiText(t) if t.parse.wikitext.text.contains("#REDIRECT") => {
+                self.get_linked_pages().ok()?.next()
             }
+            Self::WikiText(_) => None,
         }
     }
 
@@ -118,23 +160,31 @@ impl WikipediaBody {
     ///
     /// # Errors
     ///
-    /// This method fails if the serialisation of the text fails or the type of the URL is Basic
+    /// This method fails if the text is an API error or doesn't match the response of the url type
     pub fn from_url_type(
         url_type: WikipediaUrlType,
         body: String,
-    ) -> Result<WikipediaBody, serde_json::Error> {
+    ) -> Result<WikipediaBody, ResponseError> {
         match url_type {
-            WikipediaUrlType::LinksApi => {
-                serde_json::from_str::<Value>(&body).map(|response| WikipediaBody::Links(response))
-            }
-            WikipediaUrlType::RawApi => serde_json::from_str::<Value>(&body)
-                .map(|response| WikipediaBody::WikiText(response))
-                .map_err(|err| err.into()),
-            WikipediaUrlType::Basic => {
-                Err(<serde_json::Error as serde::de::Error>::custom(
-                    "Can't deserialize links from the Normal Request Type",
-                )) //TODO: Please fix this
-            }
+            WikipediaUrlType::LinksApi => Self::links_from_text(&body),
+            WikipediaUrlType::RawApi => Self::wikitext_from_text(&body),
+            WikipediaUrlType::Basic => Ok(WikipediaBody::Html(body)),
+        }
+    }
+
+    /// Get the ID of the revision the body was loaded from, if the response included it
+    ///
+    /// The wikitext carries `parse.revid`, the links carry the `lastrevid` of their page and the HTML carries `wgRevisionId`
+    pub fn revision(&self) -> Option<u64> {
+        match self {
+            Self::WikiText(wikitext) => wikitext.parse.revid,
+            Self::Links(links) => links.first_page().ok()?.lastrevid,
+            Self::Html(html) => Self::HTML_REVISION_REGEX
+                .captures(html)?
+                .get(1)?
+                .as_str()
+                .parse()
+                .ok(),
         }
     }
 
@@ -142,11 +192,12 @@ impl WikipediaBody {
     ///
     /// # Errors
     ///
-    /// This method fails if the 'title' field is not available in the deserialised JSON
-    pub fn get_pathinfo(&self) -> Result<String, PathinfoParseError> {
+    /// This method fails if the field holding the title is missing, the error names the field
+    pub fn get_pathinfo(&self) -> Result<String, ResponseError> {
         match self {
-            WikipediaBody::WikiText(wikitext) => Self::get_pathinfo_from_wikitext(&wikitext),
-            WikipediaBody::Links(links) => Self::get_pathinfo_from_links(&links),
+            WikipediaBody::WikiText(wikitext) => Ok(Self::get_pathinfo_from_wikitext(wikitext)),
+            WikipediaBody::Links(links) => Self::get_pathinfo_from_links(links),
+            WikipediaBody::Html(html) => Self::get_pathinfo_from_html(html),
         }
     }
 
@@ -156,158 +207,220 @@ impl WikipediaBody {
     ///
     /// # Errors
     ///
-    /// This method fails if the 'title' field is not available in the deserialized JSON
-    pub fn get_pathinfo_from_links(data: &serde_json::Value) -> Result<String, PathinfoParseError> {
-        data.get("query")
-            .and_then(|query| query.get("pages")?.as_object()?.iter().next())
-            .map(|(_, value)| value)
-            .and_then(|value| value.get("title")?.as_str())
-            .map(|title| title.to_string())
-            .ok_or(PathinfoParseError)
+    /// This method fails if the response has no `query.pages` field
+    pub fn get_pathinfo_from_links(data: &QueryResponse) -> Result<String, ResponseError> {
+        data.first_page().map(|page| page.title.clone())
+    }
+
+    /// Get the pathinfo of a page stored as HTML
+    ///
+    /// The pathinfo is taken from `<link rel="canonical" href="https://en.wikipedia.org/wiki/Title">`, and percent-decoded like a [Title]
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the HTML has no canonical link
+    pub fn get_pathinfo_from_html(html: &str) -> Result<String, ResponseError> {
+        Self::HTML_CANONICAL_REGEX
+            .captures(html)
+            .map(|capture| crate::title::percent_decode(capture.extract::<1>().1[0]))
+            .ok_or(ResponseError::MissingField("link rel=\"canonical\""))
     }
 
     /// Get the pathinfo of a page stored with wikitext
     ///
     /// The structure to access the title is `{parse: {title: "Title"}}`
+    pub fn get_pathinfo_from_wikitext(data: &ParseResponse) -> String {
+        data.parse.title.clone()
+    }
+
+    /// Get the linked pages of the body that [DefaultLinkFilter] keeps
     ///
     /// # Errors
     ///
-    /// This method fails if the 'title' field is not available in the deserialized JSON
-    pub fn get_pathinfo_from_wikitext(
-        data: &serde_json::Value,
-    ) -> Result<String, PathinfoParseError> {
-        data.get("parse")
-            .and_then(|value| value.get("title")?.as_str())
-            .map(|title| title.to_string())
-            .ok_or(PathinfoParseError)
+    /// This method fails if the field holding the links is missing, the error names the field
+    pub fn get_linked_pages(
+        &self,
+    ) -> Result<Box<dyn Iterator<Item = WikipediaPage> + '_>, ResponseError> {
+        self.get_linked_pages_with(&DefaultLinkFilter)
     }
 
-    /// Get the linked pages of the body
+    /// Get the linked pages of the body that a filter keeps
     ///
-    /// Returns [None] if the recieved JSON is malformed
-    pub fn get_linked_pages(&self) -> Option<Box<dyn Iterator<Item = WikipediaPage> + '_>> {
-        match self {
-            WikipediaBody::WikiText(t) => Some(Box::new(Self::get_linked_pages_from_wikitext(t)?)),
-            WikipediaBody::Links(t) => Some(Box::new(Self::get_linked_pages_from_links(t)?)),
-        }
+    /// The filter sees every [Link], so it can also keep pages by where they are linked, like [crate::SectionFilter]
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the field holding the links is missing, the error names the field
+    pub fn get_linked_pages_with<'a>(
+        &'a self,
+        filter: &'a dyn LinkFilter,
+    ) -> Result<Box<dyn Iterator<Item = WikipediaPage> + 'a>, ResponseError> {
+        Ok(Box::new(
+            self.get_links_with(filter)?
+                .into_iter()
+                .map(|link| link.page().clone())
+                .unique(),
+        ))
     }
 
     /// Get the linked pages of a body in links format
     ///
-    /// The pattern to access the linked pages is `{query: {pages: {links: [{title: "Title"}]}}}``
-    ///
-    /// Returns [None] if the recieved JSON is invalid
-    pub fn get_linked_pages_from_links(
-        value: &serde_json::Value,
-    ) -> Option<impl Iterator<Item = WikipediaPage>> {
-        value
-            .get("query")
-            .and_then(|query| query.get("pages")?.as_object()?.iter().next())
-            .map(|(_, value)| value)
-            .and_then(|data| data.get("links")?.as_array())
-            .map(|links| {
-                links.iter().filter_map(|link| {
-                    Some(WikipediaPage::from_title(link.get("title")?.as_str()?))
-                })
-            })
+    /// The pattern to access the linked pages is `{query: {pages: {links: [{title: "Title"}]}}}`
+    ///
+    /// The API doesn't say where in the article a page is linked, so filters by placement like [crate::SectionFilter] keep nothing
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the response has no `query.pages` field
+    pub fn get_linked_pages_from_links<'a>(
+        value: &'a QueryResponse,
+        filter: &'a dyn LinkFilter,
+    ) -> Result<impl Iterator<Item = WikipediaPage> + 'a, ResponseError> {
+        Ok(link::links_from_query(value)?
+            .into_iter()
+            .filter(|link| filter::keeps_link(filter, link))
+            .map(|link| link.page().clone())
+            .unique())
+    }
+
+    /// Get the linked pages of a body in HTML format
+    ///
+    /// Only links inside the article content are included
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the HTML has no article content
+    pub fn get_linked_pages_from_html<'a>(
+        html: &'a str,
+        filter: &'a dyn LinkFilter,
+    ) -> Result<impl Iterator<Item = WikipediaPage> + 'a, ResponseError> {
+        let content = &html[html
+            .find(Self::HTML_CONTENT_START)
+            .ok_or(ResponseError::MissingField("id=\"mw-content-text\""))?..];
+
+        Ok(link::links_from_html(content)
+            .into_iter()
+            .filter(|link| filter::keeps_link(filter, link))
+            .map(|link| link.page().clone())
+            .unique())
     }
 
     /// Get the linked pages of a body in (wikitext)[https://en.wikipedia.org/wiki/Help:Wikitext] format
     ///
     /// The pattern to access the wikitext pages is `{parse: {wikitext: "wikitext"}}`
+    pub fn get_linked_pages_from_wikitext<'a>(
+        value: &ParseResponse,
+        filter: &'a dyn LinkFilter,
+    ) -> impl Iterator<Item = WikipediaPage> + use<'a> {
+        link::links_from_wikitext(&value.parse.wikitext.text)
+            .into_iter()
+            .filter(|link| filter::keeps_link(filter, link))
+            .map(|link| link.page().clone())
+            .unique()
+    }
+
+    /// Get every link of the body that [DefaultLinkFilter] keeps, along with where it was found in the article
     ///
-    /// Returns [None] if the recieved JSON is invalid
-    pub fn get_linked_pages_from_wikitext(
-        value: &Value,
-    ) -> Option<impl Iterator<Item = WikipediaPage>> {
-        let page_text = value
-            .get("parse")
-            .and_then(|parse| parse.get("wikitext"))
-            .and_then(|wikitext| wikitext.as_object()?.iter().next()?.1.as_str())?;
+    /// Unlike [WikipediaBody::get_linked_pages], a page linked more than once has a [Link] for every time it is linked
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the field holding the links is missing, the error names the field
+    pub fn get_links(&self) -> Result<Vec<Link>, ResponseError> {
+        self.get_links_with(&DefaultLinkFilter)
+    }
 
-        Some(
-            Self::WIKITEXT_LINK_REGEX
-                .captures_iter(&page_text)
-                .map(|capture| capture.extract::<1>())
-                .unique_by(|capture_data| capture_data.1[0])
-                .filter(|capture_data| {
-                    Self::FILE_ENDINGS
-                        .iter()
-                        .all(|page| !capture_data.0.contains(page))
-                })
-                .filter(|capture_data| {
-                    Self::FILTERED_PAGES
-                        .iter()
-                        .all(|page| !capture_data.0.contains(page))
-                })
-                .map(|capture_data| WikipediaPage::from_title(capture_data.1[0])),
-        )
+    /// Get every link of the body that a filter keeps, along with where it was found in the article
+    ///
+    /// The filter is composed with the defaults, see [LinkFilter].
+    /// Links of [WikipediaBody::Links] have no placement, so filters by placement like [crate::SectionFilter] keep none of them
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the field holding the links is missing, the error names the field
+    pub fn get_links_with(&self, filter: &dyn LinkFilter) -> Result<Vec<Link>, ResponseError> {
+        let links = match self {
+            WikipediaBody::WikiText(t) => link::links_from_wikitext(&t.parse.wikitext.text),
+            WikipediaBody::Links(t) => link::links_from_query(t)?,
+            WikipediaBody::Html(t) => link::links_from_html(
+                &t[t.find(Self::HTML_CONTENT_START)
+                    .ok_or(ResponseError::MissingField("id=\"mw-content-text\""))?..],
+            ),
+        };
+
+        Ok(links
+            .into_iter()
+            .filter(|link| filter::keeps_link(filter, link))
+            .collect())
+    }
+
+    /// Get every wikilink of a body in wikitext format, including links to categories, files and other languages
+    pub fn get_wikilinks_from_wikitext(value: &ParseResponse) -> Vec<WikiLink> {
+        WikiLink::parse_all(&value.parse.wikitext.text)
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+/// The kind of URL a page is requested from, which decides the format of its [WikipediaBody]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum WikipediaUrlType {
+    /// The article itself, like <https://en.wikipedia.org/wiki/Waffle>, giving [WikipediaBody::Html]
     Basic,
+    /// The parse API, giving [WikipediaBody::WikiText]
     RawApi,
+    /// The query API, giving [WikipediaBody::Links]
     LinksApi,
 }
 
 impl WikipediaUrlType {
-    pub fn base_url(&self, language: WikiLanguage) -> Result<Url, WikipediaLanguageInvalidError> {
+    /// Get the URL that every URL of this type is built on
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the language has no edition of the project
+    pub fn base_url(
+        &self,
+        project: WikiProject,
+        language: WikiLanguage,
+    ) -> Result<Url, WikipediaLanguageInvalidError> {
+        let host = project
+            .host(language)
+            .ok_or(WikipediaLanguageInvalidError)?;
+
         Ok(match self {
-            Self::Basic => Url::parse(
-                format!(
-                    "https://{}.wikipedia.org/wiki/",
-                    language
-                        .as_code_wiki()
-                        .ok_or(WikipediaLanguageInvalidError)?
-                )
-                .as_str(),
-            )
-            .expect(
-                format!(
-                    "Base Wikipedia URL with language '{:?}' parsing failed",
-                    language
-                )
-                .as_str(),
-            ),
-            Self::LinksApi | Self::RawApi => Url::parse(
-                format!(
-                    "https://{}.wikipedia.org/w/api.php",
-                    language
-                        .as_code_wiki()
-                        .ok_or(WikipediaLanguageInvalidError)?
-                )
-                .as_str(),
-            )
-            .expect(
-                format!(
-                    "Base Wikipedia API URL with language '{:?}' parsing failed",
-                    language
-                )
-                .as_str(),
-            ),
+            Self::Basic => Url::parse(format!("https://{host}/wiki/").as_str())
+                .unwrap_or_else(|_| panic!("Base URL of '{host}' parsing failed")),
+            Self::LinksApi | Self::RawApi => {
+                Url::parse(format!("https://{host}/w/api.php").as_str())
+                    .unwrap_or_else(|_| panic!("Base API URL of '{host}' parsing failed"))
+            }
         })
     }
 
+    /// Get the URL of the page with this pathinfo
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the language has no edition of the project
     pub fn url_with(
         &self,
+        project: WikiProject,
         language: WikiLanguage,
         pathinfo: &String,
     ) -> Result<Url, WikipediaLanguageInvalidError> {
         match self {
-            WikipediaUrlType::Basic => Ok(self.base_url(language)?.join(&pathinfo).expect(
-                format!(
-                    "Wikipedia URL for '{}' with language '{:?}' parsing failed",
-                    pathinfo, language
-                )
-                .as_str(),
-            )),
+            // Relative to the base, or a namespace like `Cookbook:Waffles` would be read as the scheme
+            WikipediaUrlType::Basic => {
+                Ok(self.base_url(project, language)?.join(&format!("./{pathinfo}")).unwrap_or_else(|_| {
+                    panic!(
+                        "URL for '{pathinfo}' on {project} with language '{language:?}' parsing failed"
+                    )
+                }))
+            }
             WikipediaUrlType::RawApi => {
-                let mut url = self.base_url(language)?;
+                let mut url = self.base_url(project, language)?;
                 url.set_query(Some(
                     format!(
-                        "origin=*&action=parse&prop=wikitext&format=json&page={}",
+                        "origin=*&action=parse&prop=wikitext|revid&format=json&page={}",
                         pathinfo
                     )
                     .as_str(),
@@ -315,10 +428,10 @@ impl WikipediaUrlType {
                 Ok(url)
             }
             WikipediaUrlType::LinksApi => {
-                let mut url = self.base_url(language)?;
+                let mut url = self.base_url(project, language)?;
                 url.set_query(Some(
                     format!(
-                        "action=query&format=json&prop=links&pllimit=500&origin=*&titles={}",
+                        "action=query&format=json&prop=links|info&pllimit=500&origin=*&titles={}",
                         pathinfo
                     )
                     .as_str(),
@@ -327,23 +440,157 @@ impl WikipediaUrlType {
             }
         }
     }
+
+    /// Get the links API URL for several pages at once, redirects are resolved by the API
+    ///
+    /// The API only accepts up to 50 titles per request
+    pub fn batch_links_url(
+        project: WikiProject,
+        language: WikiLanguage,
+        titles: &[String],
+    ) -> Result<Url, WikipediaLanguageInvalidError> {
+        let mut url = Self::LinksApi.base_url(project, language)?;
+
+        url.set_query(Some(
+            "action=query&format=json&prop=links|info&pllimit=500&redirects=1&origin=*",
+        ));
+
+        url.query_pairs_mut()
+            .append_pair("titles", titles.join("|").as_str());
+
+        Ok(url)
+    }
+
+    /// Get the query API URL for the latest revision of several pages at once, without their links
+    ///
+    /// The API only accepts up to 50 titles per request
+    pub fn info_url(
+        project: WikiProject,
+        language: WikiLanguage,
+        titles: &[String],
+    ) -> Result<Url, WikipediaLanguageInvalidError> {
+        let mut url = Self::LinksApi.base_url(project, language)?;
+
+        url.set_query(Some("action=query&format=json&prop=info&origin=*"));
+
+        url.query_pairs_mut()
+            .append_pair("titles", titles.join("|").as_str());
+
+        Ok(url)
+    }
+
+    /// Get the query API URL for the plain text introduction, description and thumbnail of a page
+    ///
+    /// Redirects are resolved by the API, so the summary of a redirect is the summary of its target
+    pub fn summary_url(
+        project: WikiProject,
+        language: WikiLanguage,
+        pathinfo: &str,
+    ) -> Result<Url, WikipediaLanguageInvalidError> {
+        let mut url = Self::LinksApi.base_url(project, language)?;
+
+        url.set_query(Some(
+            "action=query&format=json&prop=extracts|description|pageimages&exintro=1&explaintext=1&piprop=thumbnail&pithumbsize=320&redirects=1&origin=*",
+        ));
+
+        url.query_pairs_mut().append_pair("titles", pathinfo);
+
+        Ok(url)
+    }
+
+    /// Get the query API URL that resolves several titles at once, following redirects without loading the pages
+    ///
+    /// The API only accepts up to 50 titles per request
+    pub fn resolve_url(
+        project: WikiProject,
+        language: WikiLanguage,
+        titles: &[String],
+    ) -> Result<Url, WikipediaLanguageInvalidError> {
+        let mut url = Self::LinksApi.base_url(project, language)?;
+
+        url.set_query(Some("action=query&format=json&redirects=1&origin=*"));
+
+        url.query_pairs_mut()
+            .append_pair("titles", titles.join("|").as_str());
+
+        Ok(url)
+    }
+
+    /// Get the query API URL for the same page on the wikipedias of other languages
+    ///
+    /// Redirects are resolved by the API, so the interlanguage links of a redirect are the links of its target
+    pub fn langlinks_url(
+        project: WikiProject,
+        language: WikiLanguage,
+        pathinfo: &str,
+    ) -> Result<Url, WikipediaLanguageInvalidError> {
+        let mut url = Self::LinksApi.base_url(project, language)?;
+
+        url.set_query(Some(
+            "action=query&format=json&prop=langlinks&lllimit=max&redirects=1&origin=*",
+        ));
+
+        url.query_pairs_mut().append_pair("titles", pathinfo);
+
+        Ok(url)
+    }
+
+    /// Get the query API URL for the articles that link to a page, skipping redirects
+    pub fn backlinks_url(
+        project: WikiProject,
+        language: WikiLanguage,
+        pathinfo: &str,
+    ) -> Result<Url, WikipediaLanguageInvalidError> {
+        let mut url = Self::LinksApi.base_url(project, language)?;
+
+        url.set_query(Some(
+            "action=query&format=json&list=backlinks&blnamespace=0&blfilterredir=nonredirects&bllimit=500&origin=*",
+        ));
+
+        url.query_pairs_mut().append_pair("bltitle", pathinfo);
+
+        Ok(url)
+    }
 }
 
-fn verify_url(url: &Url) -> Result<(), WikipediaUrlError> {
+/// Check that a URL leads to an article on a wiki project, returning the project
+fn verify_url(url: &Url) -> Result<WikiProject, WikipediaUrlError> {
     let host_str = url.host_str().unwrap_or("");
 
-    if !host_str.ends_with("wikipedia.org") || !(url.scheme() == "http" || url.scheme() == "https")
-    {
-        return Err(WikipediaUrlError::InvalidHost);
-    }
+    let project = WikiProject::ALL
+        .into_iter()
+        .find(|project| {
+            host_str == project.domain()
+                || host_str
+                    .strip_suffix(project.domain())
+                    .is_some_and(|subdomain| subdomain.ends_with('.'))
+        })
+        .filter(|_| url.scheme() == "http" || url.scheme() == "https")
+        .ok_or(WikipediaUrlError::InvalidHost)?;
 
     let mut path = url.path_segments().ok_or(WikipediaUrlError::InvalidPath)?;
 
-    if !path.next().map_or(false, |path| path == "wiki") {
+    if path.next().is_none_or(|path| path != "wiki") {
         return Err(WikipediaUrlError::InvalidPath);
     }
 
-    Ok(())
+    Ok(project)
+}
+
+/// Get the language of a host on a project from its subdomain, `wikipedia.org` and `www.wikipedia.org` are English
+///
+/// Mobile hosts like `de.m.wikipedia.org` have the same language as their desktop hosts
+fn language_from_host(host: &str, project: WikiProject) -> Result<WikiLanguage, WikipediaUrlError> {
+    if host == project.domain() {
+        return Ok(WikiLanguage::English);
+    }
+
+    match host.split('.').next() {
+        None | Some("www") => Ok(WikiLanguage::English),
+        Some(code) => WikiLanguage::from_code(code)
+            .filter(|language| project.language_code(language).is_some())
+            .ok_or_else(|| WikipediaUrlError::InvalidLanguage(code.to_string())),
+    }
 }
 
 impl WikipediaPage {
@@ -356,7 +603,10 @@ impl WikipediaPage {
         self.body = Some(data);
 
         match pathinfo_new {
-            Ok(pathinfo) => self.pathinfo = pathinfo,
+            Ok(pathinfo) => {
+                self.title = Title::for_project(&pathinfo, self.project);
+                self.pathinfo = pathinfo;
+            }
             Err(e) => log::error!("{e}"),
         }
 
@@ -371,6 +621,130 @@ impl WikipediaPage {
         }
     }
 
+    /// Set the namespace of the page from the `ns` field of an API response
+    pub(crate) fn with_namespace_id(self, id: i64) -> Self {
+        Self {
+            namespace: Some(Namespace::from_id(id)),
+            ..self
+        }
+    }
+
+    /// Set the titles of the pages that redirected to this page, in the order they were followed
+    pub fn with_redirects(self, redirects: Vec<String>) -> Self {
+        Self { redirects, ..self }
+    }
+
+    /// Add a title that redirects to this page, unless it is already known
+    #[cfg(all(feature = "client", feature = "graphs"))]
+    pub(crate) fn push_redirect(&mut self, title: String) {
+        if !self.redirects.contains(&title)
+            && Title::for_project(&title, self.project) != self.title
+        {
+            self.redirects.push(title);
+        }
+    }
+
+    /// Rename the page to the title it redirects to, keeping the old title as a redirect
+    #[cfg(all(feature = "client", feature = "graphs"))]
+    pub(crate) fn rename_to(&mut self, title: &Title) {
+        if self.title == *title {
+            return;
+        }
+
+        let old = std::mem::replace(&mut self.title, title.clone());
+
+        self.pathinfo = title.pathinfo();
+
+        self.push_redirect(old.as_str().to_string());
+    }
+
+    /// Get the titles of the pages that redirected to this page, in the order they were followed
+    ///
+    /// For example, loading 'USA' gives the 'United States' page with the redirects `["USA"]`
+    pub fn redirects(&self) -> &[String] {
+        &self.redirects
+    }
+
+    /// Set the pages that link to this page
+    pub fn with_backlinks(self, backlinks: Vec<WikipediaPage>) -> Self {
+        Self {
+            backlinks: Some(backlinks),
+            ..self
+        }
+    }
+
+    /// Get the pages that link to this page if they are loaded
+    pub fn try_get_backlinks(&self) -> Option<&[WikipediaPage]> {
+        self.backlinks.as_deref()
+    }
+
+    /// Set the pages that are this page on the wikipedias of other languages
+    pub fn with_langlinks(self, langlinks: Vec<WikipediaPage>) -> Self {
+        Self {
+            langlinks: Some(langlinks),
+            ..self
+        }
+    }
+
+    /// Get the pages that are this page on the wikipedias of other languages if they are loaded
+    pub fn try_get_langlinks(&self) -> Option<&[WikipediaPage]> {
+        self.langlinks.as_deref()
+    }
+
+    /// Get this page on the wikipedia of another language if the interlanguage links are loaded and it has one
+    pub fn try_get_langlink(&self, language: WikiLanguage) -> Option<&WikipediaPage> {
+        self.langlinks
+            .as_ref()?
+            .iter()
+            .find(|page| page.language == language)
+    }
+
+    /// Set the summary of this page
+    pub fn with_summary(self, summary: PageSummary) -> Self {
+        Self {
+            summary: Some(summary),
+            ..self
+        }
+    }
+
+    /// Get the summary of this page if it is loaded
+    pub fn try_get_summary(&self) -> Option<&PageSummary> {
+        self.summary.as_ref()
+    }
+
+    /// Set the `ETag` header the body of this page was served with
+    pub fn with_etag(self, etag: impl Into<String>) -> Self {
+        Self {
+            etag: Some(etag.into()),
+            ..self
+        }
+    }
+
+    /// Get the `ETag` header the body of this page was served with, if the server sent one
+    ///
+    /// This is used by [WikipediaClient::refresh] to only download the body again if it changed
+    pub fn etag(&self) -> Option<&str> {
+        self.etag.as_deref()
+    }
+
+    /// Get the ID of the revision the body of this page was loaded from, if it is known
+    pub fn revision(&self) -> Option<u64> {
+        self.body.as_ref()?.revision()
+    }
+
+    /// Check if two pages are the same page, either by title or because one redirected to the other
+    pub fn is_same_page(&self, other: &WikipediaPage) -> bool {
+        let redirected = |page: &WikipediaPage, target: &Title| {
+            page.redirects
+                .iter()
+                .any(|redirect| Title::for_project(redirect, page.project) == *target)
+        };
+
+        self.language == other.language
+            && self.project == other.project
+            && (self == other || redirected(self, &other.title) || redirected(other, &self.title))
+    }
+
     /// Check if the page text is loaded
     pub fn is_page_text_loaded(&self) -> bool {
         self.body.is_some()
@@ -381,27 +755,71 @@ impl WikipediaPage {
         &self.pathinfo
     }
 
-    /// Get the url of the wikipedia page with a certain language
+    /// Get the language of the wiki the page is on
+    pub fn language(&self) -> WikiLanguage {
+        self.language
+    }
+
+    /// Set the language of the wiki the page is on
+    pub fn with_language(self, language: WikiLanguage) -> Self {
+        Self { language, ..self }
+    }
+
+    /// Get the project of the wiki the page is on
+    pub fn project(&self) -> WikiProject {
+        self.project
+    }
+
+    /// Set the project of the wiki the page is on, like [WikiProject::Wiktionary]
+    pub fn with_project(self, project: WikiProject) -> Self {
+        Self {
+            title: Title::for_project(&self.pathinfo, project),
+            project,
+            ..self
+        }
+    }
+
+    /// Get the url of the page on its own project, in its own language
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the language of the page has no edition of its project
+    pub fn url(&self) -> Result<Url, WikipediaLanguageInvalidError> {
+        self.url_with_lang(self.language)
+    }
+
+    /// Get the url of the page on its own project with a certain language
+    ///
+    /// The page is not translated, this is only the same title on another wiki, see [WikipediaPage::url]
     pub fn url_with_lang(
         &self,
         language: WikiLanguage,
     ) -> Result<Url, WikipediaLanguageInvalidError> {
-        WikipediaUrlType::Basic.url_with(language, &self.pathinfo)
+        WikipediaUrlType::Basic.url_with(self.project, language, &self.pathinfo)
     }
 
-    /// Create a new WikipediaPage from the title
+    /// Create a new WikipediaPage from the title, on the English wikipedia
     ///
-    /// For example: `Waffle` to the the Waffle page
+    /// For example: `Waffle` to the the Waffle page, see [WikipediaPage::with_language] and [WikipediaPage::with_project] for other wikis
     pub fn from_title(title: impl Into<String>) -> Self {
         let title: String = title.into();
 
         WikipediaPage {
             pathinfo: title.replace(" ", "_"),
+            title: Title::new(&title),
+            namespace: None,
+            language: WikiLanguage::English,
+            project: WikiProject::Wikipedia,
             body: None,
+            redirects: Vec::new(),
+            backlinks: None,
+            langlinks: None,
+            etag: None,
+            summary: None,
         }
     }
 
-    /// Try to create a new WikipediaPage from a path
+    /// Try to create a new WikipediaPage from a path, on the English wikipedia
     ///
     /// For example: `/wiki/Waffle/` to get the Waffle page
     pub fn try_from_path(path: impl Into<String>) -> Result<Self, WikipediaUrlError> {
@@ -414,17 +832,20 @@ impl WikipediaPage {
 
         let title = joined
             .path_segments()
-            .and_then(|segments| segments.last())
+            .and_then(|mut segments| segments.next_back())
             .ok_or(WikipediaUrlError::InvalidPath)?;
 
         Ok(Self::from_title(title))
     }
 
-    /// Try to create a new WikipediaPage from a URL
+    /// Try to create a new WikipediaPage from a URL, on the project of its domain in the language of its subdomain
     ///
-    /// For example: `https://wikipedia.org/wiki/Waffle` to get the Waffle page
+    /// For example: `https://de.wikipedia.org/wiki/Waffel` to get the German Waffel page,
+    /// or `https://en.wiktionary.org/wiki/waffle` to get the waffle entry of the English Wiktionary
     pub fn try_from_url(url: Url) -> Result<Self, WikipediaUrlError> {
-        verify_url(&url)?;
+        let project = verify_url(&url)?;
+
+        let language = language_from_host(url.host_str().unwrap_or(""), project)?;
 
         let mut base = Url::parse(url.origin().ascii_serialization().as_str())
             .expect("Origin should always be a valid URL");
@@ -434,8 +855,17 @@ impl WikipediaPage {
         base.make_relative(&url)
             .ok_or(WikipediaUrlError::InvalidPath)
             .map(|val| WikipediaPage {
+                title: Title::for_project(&val, project),
+                namespace: None,
                 pathinfo: val,
+                language,
+                project,
                 body: None,
+                redirects: Vec::new(),
+                backlinks: None,
+                langlinks: None,
+                etag: None,
+                summary: None,
             })
     }
 
@@ -461,7 +891,9 @@ impl WikipediaPage {
                 client.random_page(callback)
             }
 
-            /// Load the page text if it is not already stored in memory
+            /// Load the page text, replacing any body that is already stored in memory
+            ///
+            /// If the page is a redirect, the loaded page takes the title of the page the redirects lead to, see [WikipediaPage::redirects]
             ///
             /// *This method requires the `client` feature*
             ///
@@ -469,10 +901,223 @@ impl WikipediaPage {
             ///
             /// This method fails if the request for the page data fails
             pub fn load_page_text(&self, client: &WikipediaClient, callback: impl Fn(Result<Self, HttpError>) + Send + Clone + 'static) -> Result<(), WikipediaLanguageInvalidError> {
-                let title = self.title();
+                self.url()?;
 
-                client
-                    .get(self.pathinfo.clone(), move |response| callback(response.map(|body| WikipediaPage::from_title(title.clone()).with_body(body))))
+                crate::client::spawn_with_callback(self.load_page_text_async(client), callback);
+
+                Ok(())
+            }
+
+            /// Get a random and unloaded page from the wikimedia API
+            ///
+            /// *This method requires the `client` feature*
+            ///
+            /// # Errors
+            ///
+            /// This method fails if the request for a random page fails
+            pub fn random_async(client: &WikipediaClient) -> impl Future<Output = Result<WikipediaPage, HttpError>> + Send + 'static {
+                client.random_page_async()
+            }
+
+            /// Load the page text, replacing any body that is already stored in memory
+            ///
+            /// The page is loaded from the wiki of its own project and language, whatever the project and language of the client are.
+            /// If the page is a redirect, the loaded page takes the title of the page the redirects lead to, see [WikipediaPage::redirects].
+            /// The backlinks, langlinks and summary that were already loaded are kept
+            ///
+            /// *This method requires the `client` feature*
+            ///
+            /// # Errors
+            ///
+            /// This method fails if the request for the page data fails
+            pub fn load_page_text_async(&self, client: &WikipediaClient) -> impl Future<Output = Result<Self, HttpError>> + Send + 'static {
+                let page = Self { body: None, ..self.clone() };
+
+                let client = client.for_wiki(self.project, self.language);
+
+                async move {
+                    let loaded = client.get_with_redirects_async(page.pathinfo.clone(), true).await?;
+
+                    Ok(page.with_loaded(loaded))
+                }
+            }
+
+            /// Replace the body of the page with a freshly loaded one, appending the redirects that were followed
+            pub(crate) fn with_loaded(mut self, loaded: LoadedBody) -> Self {
+                self.set_page_body(loaded.body);
+
+                for redirect in loaded.redirects {
+                    if !self.redirects.contains(&redirect) {
+                        self.redirects.push(redirect);
+                    }
+                }
+
+                Self { etag: loaded.etag, ..self }
+            }
+
+            /// Load the pages that link to this page, keeping the rest of the page as it is
+            ///
+            /// *This method requires the `client` feature*
+            ///
+            /// # Errors
+            ///
+            /// This method fails if the page's language has no valid URL
+            pub fn load_backlinks(&self, client: &WikipediaClient, callback: impl Fn(Result<Self, HttpError>) + Send + 'static) -> Result<(), WikipediaLanguageInvalidError> {
+                WikipediaUrlType::LinksApi.base_url(self.project, self.language)?;
+
+                crate::client::spawn_with_callback(self.load_backlinks_async(client), callback);
+
+                Ok(())
+            }
+
+            /// Load the pages that link to this page, keeping the rest of the page as it is
+            ///
+            /// *This method requires the `client` feature*
+            ///
+            /// # Errors
+            ///
+            /// This method fails if the request for the backlinks fails
+            pub fn load_backlinks_async(&self, client: &WikipediaClient) -> impl Future<Output = Result<Self, HttpError>> + Send + 'static {
+                let page = self.clone();
+
+                let client = client.for_wiki(self.project, self.language);
+
+                async move {
+                    let backlinks = client.get_backlinks_async(page.pathinfo()).await?;
+
+                    Ok(page.with_backlinks(backlinks))
+                }
+            }
+
+            /// Load the pages that are this page on the wikipedias of other languages, keeping the rest of the page as it is
+            ///
+            /// *This method requires the `client` feature*
+            ///
+            /// # Errors
+            ///
+            /// This method fails if the page's language has no valid URL
+            pub fn load_langlinks(&self, client: &WikipediaClient, callback: impl Fn(Result<Self, HttpError>) + Send + 'static) -> Result<(), WikipediaLanguageInvalidError> {
+                WikipediaUrlType::LinksApi.base_url(self.project, self.language)?;
+
+                crate::client::spawn_with_callback(self.load_langlinks_async(client), callback);
+
+                Ok(())
+            }
+
+            /// Load the pages that are this page on the wikipedias of other languages, keeping the rest of the page as it is
+            ///
+            /// *This method requires the `client` feature*
+            ///
+            /// # Errors
+            ///
+            /// This method fails if the request for the interlanguage links fails, or the page doesn't exist
+            pub fn load_langlinks_async(&self, client: &WikipediaClient) -> impl Future<Output = Result<Self, HttpError>> + Send + 'static {
+                let page = self.clone();
+
+                let client = client.for_wiki(self.project, self.language);
+
+                async move {
+                    let langlinks = client.get_langlinks_async(page.pathinfo()).await?;
+
+                    Ok(page.with_langlinks(langlinks))
+                }
+            }
+
+            /// Load the summary of this page, keeping the rest of the page as it is
+            ///
+            /// *This method requires the `client` feature*
+            ///
+            /// # Errors
+            ///
+            /// This method fails if the page's language has no valid URL
+            pub fn load_summary(&self, client: &WikipediaClient, callback: impl Fn(Result<Self, HttpError>) + Send + 'static) -> Result<(), WikipediaLanguageInvalidError> {
+                WikipediaUrlType::LinksApi.base_url(self.project, self.language)?;
+
+                crate::client::spawn_with_callback(self.load_summary_async(client), callback);
+
+                Ok(())
+            }
+
+            /// Load the summary of this page, keeping the rest of the page as it is
+            ///
+            /// *This method requires the `client` feature*
+            ///
+            /// # Errors
+            ///
+            /// This method fails if the request for the summary fails, or the page doesn't exist
+            pub fn load_summary_async(&self, client: &WikipediaClient) -> impl Future<Output = Result<Self, HttpError>> + Send + 'static {
+                let page = self.clone();
+
+                let client = client.for_wiki(self.project, self.language);
+
+                async move {
+                    let summary = client.get_summary_async(page.pathinfo()).await?;
+
+                    Ok(page.with_summary(summary))
+                }
+            }
+
+            /// Load the links of several pages, batching them into as few requests as possible
+            ///
+            /// Executes the callback with a result for each page, in the order they were given
+            ///
+            /// *This method requires the `client` feature*
+            ///
+            /// # Errors
+            ///
+            /// This method fails if the language of any of the pages has no valid URL
+            pub fn load_many(pages: &[WikipediaPage], client: &WikipediaClient, callback: impl Fn(Vec<Result<Self, HttpError>>) + Send + 'static) -> Result<(), WikipediaLanguageInvalidError> {
+                for page in pages {
+                    WikipediaUrlType::LinksApi.base_url(page.project, page.language)?;
+                }
+
+                crate::client::spawn_with_callback(Self::load_many_async(pages, client), callback);
+
+                Ok(())
+            }
+
+            /// Load the links of several pages, batching them into as few requests as possible
+            ///
+            /// Resolves with a result for each page, in the order they were given.
+            /// Pages in different languages or projects are requested from their own wikis
+            ///
+            /// *This method requires the `client` feature*
+            pub fn load_many_async(pages: &[WikipediaPage], client: &WikipediaClient) -> impl Future<Output = Vec<Result<Self, HttpError>>> + Send + 'static {
+                let len = pages.len();
+
+                let batches = pages
+                    .iter()
+                    .enumerate()
+                    .into_group_map_by(|(_, page)| (page.project, page.language))
+                    .into_iter()
+                    .map(|((project, language), pages)| {
+                        let (indices, titles): (Vec<usize>, Vec<String>) =
+                            pages.into_iter().map(|(index, page)| (index, page.title())).unzip();
+
+                        let client = client.for_wiki(project, language);
+
+                        async move { (project, language, indices, client.get_many_with_redirects_async(titles).await) }
+                    })
+                    .collect::<Vec<_>>();
+
+                async move {
+                    let mut loaded: Vec<Option<Result<Self, HttpError>>> = (0..len).map(|_| None).collect();
+
+                    for (project, language, indices, responses) in crate::client::join_all(batches).await {
+                        for (index, (title, result)) in indices.into_iter().zip(responses) {
+                            loaded[index] = Some(result.map(|(body, redirects)| {
+                                let mut page = WikipediaPage::from_title(title).with_language(language).with_project(project);
+
+                                page.set_page_body(body);
+
+                                page.with_redirects(redirects)
+                            }));
+                        }
+                    }
+
+                    // Every page is in a batch, but a batch could answer for fewer titles than it asked for
+                    loaded.into_iter().map(|page| page.unwrap_or(Err(HttpError::NoPageBody))).collect()
+                }
             }
         }
     }
@@ -491,13 +1136,66 @@ impl WikipediaPage {
         self.body.clone()
     }
 
-    /// Give a best guess at the title of the page
+    /// Give a best guess at the title of the page, see [Title]
     pub fn title(&self) -> String {
-        url_encor::decode(self.pathinfo.replace("_", " ").as_str())
+        self.title.to_string()
     }
 
-    /// Get all the pages that this page links to if the page text is loaded
+    /// Get the canonical title of the page, which decides if two pages are equal
+    pub fn canonical_title(&self) -> &Title {
+        &self.title
+    }
+
+    /// Get the namespace of the page
+    ///
+    /// Pages from API responses use the namespace the API sent, any other page gets it from the prefix of its title.
+    /// Only English prefixes are known, so `Kategorie:Dessert` on the German wiki is in [Namespace::Main] unless the API said otherwise
+    pub fn namespace(&self) -> Namespace {
+        self.namespace
+            .clone()
+            .unwrap_or_else(|| self.title.namespace())
+    }
+
+    /// Get all the pages that this page links to that [DefaultLinkFilter] keeps, if the page text is loaded
+    ///
+    /// The linked pages are on the wiki of this page
     pub fn try_get_linked_pages(&self) -> Option<Box<dyn Iterator<Item = WikipediaPage> + '_>> {
-        self.body.as_ref().map(|body| body.get_linked_pages())?
+        self.try_get_linked_pages_with(&DefaultLinkFilter)
+    }
+
+    /// Get every link of this page that [DefaultLinkFilter] keeps if the page text is loaded, see [WikipediaBody::get_links]
+    ///
+    /// The linked pages are on the wiki of this page
+    pub fn try_get_links(&self) -> Option<Vec<Link>> {
+        self.try_get_links_with(&DefaultLinkFilter)
+    }
+
+    /// Get every link of this page that a filter keeps if the page text is loaded, see [WikipediaBody::get_links_with]
+    ///
+    /// The linked pages are on the wiki of this page, except interwiki links like `fr:Gaufre`,
+    /// which lead to the wiki in their language. Interwiki links to other projects are skipped
+    pub fn try_get_links_with(&self, filter: &dyn LinkFilter) -> Option<Vec<Link>> {
+        let links = self.body.as_ref()?.get_links_with(filter).ok()?;
+
+        Some(
+            links
+                .into_iter()
+                .filter_map(|link| link.on_wiki(self.project, self.language))
+                .collect(),
+        )
+    }
+
+    /// Get all the pages that this page links to that a filter keeps, if the page text is loaded
+    ///
+    /// The linked pages are on the wiki of this page, except interwiki links, see [WikipediaPage::try_get_links_with]
+    pub fn try_get_linked_pages_with<'a>(
+        &'a self,
+        filter: &'a dyn LinkFilter,
+    ) -> Option<Box<dyn Iterator<Item = WikipediaPage> + 'a>> {
+        let links = self.try_get_links_with(filter)?;
+
+        Some(Box::new(
+            links.into_iter().map(|link| link.page().clone()).unique(),
+        ))
     }
 }
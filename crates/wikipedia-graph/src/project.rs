@@ -0,0 +1,105 @@
+use crate::WikiLanguage;
+use std::fmt::Display;
+
+/// A Wikimedia project with an edition per language, like Wikipedia or Wiktionary
+///
+/// Every project is a wiki, so pages, links and the API work the same on all of them.
+/// The project and a [WikiLanguage] decide the host, like `de.wiktionary.org`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum WikiProject {
+    /// The encyclopedia, at `wikipedia.org`
+    #[default]
+    Wikipedia,
+    /// The dictionary, at `wiktionary.org`
+    Wiktionary,
+    /// The textbooks, at `wikibooks.org`
+    Wikibooks,
+    /// The news, at `wikinews.org`
+    Wikinews,
+    /// The quotations, at `wikiquote.org`
+    Wikiquote,
+    /// The source texts, at `wikisource.org`
+    Wikisource,
+    /// The learning resources, at `wikiversity.org`
+    Wikiversity,
+    /// The travel guides, at `wikivoyage.org`
+    Wikivoyage,
+}
+
+impl WikiProject {
+    /// Every project
+    pub const ALL: [WikiProject; 8] = [
+        Self::Wikipedia,
+        Self::Wiktionary,
+        Self::Wikibooks,
+        Self::Wikinews,
+        Self::Wikiquote,
+        Self::Wikisource,
+        Self::Wikiversity,
+        Self::Wikivoyage,
+    ];
+
+    /// The name of the project, like `Wiktionary`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Wikipedia => "Wikipedia",
+            Self::Wiktionary => "Wiktionary",
+            Self::Wikibooks => "Wikibooks",
+            Self::Wikinews => "Wikinews",
+            Self::Wikiquote => "Wikiquote",
+            Self::Wikisource => "Wikisource",
+            Self::Wikiversity => "Wikiversity",
+            Self::Wikivoyage => "Wikivoyage",
+        }
+    }
+
+    /// The domain every language of the project is a subdomain of, like `wiktionary.org`
+    pub fn domain(&self) -> &'static str {
+        match self {
+            Self::Wikipedia => "wikipedia.org",
+            Self::Wiktionary => "wiktionary.org",
+            Self::Wikibooks => "wikibooks.org",
+            Self::Wikinews => "wikinews.org",
+            Self::Wikiquote => "wikiquote.org",
+            Self::Wikisource => "wikisource.org",
+            Self::Wikiversity => "wikiversity.org",
+            Self::Wikivoyage => "wikivoyage.org",
+        }
+    }
+
+    /// Check if the first letter of titles is always uppercase, Wiktionary has separate pages for `Turkey` and `turkey`
+    pub fn capitalizes_titles(&self) -> bool {
+        !matches!(self, Self::Wiktionary)
+    }
+
+    /// Get the subdomain of a language on the project, or [None] if the language has no edition of the project
+    pub fn language_code<'a>(&self, language: &'a WikiLanguage) -> Option<&'a str> {
+        match self {
+            Self::Wikipedia => language.as_code_wiki(),
+            Self::Wiktionary => language.as_code_wiktionary(),
+            Self::Wikibooks => language.as_code_wikibooks(),
+            Self::Wikinews => language.as_code_wikinews(),
+            Self::Wikiquote => language.as_code_wikiquote(),
+            Self::Wikisource => language.as_code_wikisource(),
+            Self::Wikiversity => language.as_code_wikiversity(),
+            Self::Wikivoyage => language.as_code_wikivoyage(),
+        }
+    }
+
+    /// Get the host of a language on the project, like `de.wiktionary.org`
+    ///
+    /// Returns [None] if the language has no edition of the project
+    pub fn host(&self, language: WikiLanguage) -> Option<String> {
+        Some(format!(
+            "{}.{}",
+            self.language_code(&language)?,
+            self.domain()
+        ))
+    }
+}
+
+impl Display for WikiProject {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
@@ -0,0 +1,388 @@
+use serde::de::{DeserializeOwned, IgnoredAny};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+/// An error that may occur when reading a response from the API
+#[derive(Debug, Error, Clone)]
+pub enum ResponseError {
+    /// The response isn't JSON, or doesn't have the shape of the expected response
+    #[error("Failed to deserialise response: {0}")]
+    Deserialisation(String), // serde_json::Error doesn't implement Clone
+    /// The API responded with an `error` object instead of a result
+    #[error("API error '{code}': {info}")]
+    Api {
+        /// The machine readable error code, like `missingtitle`
+        code: String,
+        /// The human readable description of the error
+        info: String,
+    },
+    /// The API sent a `warnings` object along with the result
+    #[error("API warning from '{module}': {info}")]
+    Warning {
+        /// The module of the API that sent the warning, like `main` or `query`
+        module: String,
+        /// The human readable description of the warning
+        info: String,
+    },
+    /// A field the value depends on is missing from the response
+    #[error("Response has no '{0}' field")]
+    MissingField(&'static str),
+}
+
+/// The `error` object the API responds with when a request fails
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ApiErrorBody {
+    /// The machine readable error code, like `missingtitle`
+    pub code: String,
+    /// The human readable description of the error
+    #[serde(default)]
+    pub info: String,
+}
+
+/// A single warning in the `warnings` object of a response
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ApiWarning {
+    /// The text of the warning
+    #[serde(rename = "*", alias = "warnings", default)]
+    pub text: String,
+}
+
+/// The warnings of a response, keyed by the module that sent them
+pub type ApiWarnings = BTreeMap<String, ApiWarning>;
+
+/// The parts every response may have, regardless of what was requested
+#[derive(Deserialize)]
+struct Envelope {
+    #[serde(default)]
+    error: Option<ApiErrorBody>,
+    #[serde(default)]
+    warnings: ApiWarnings,
+}
+
+/// Turn the warnings of a response into [ResponseError::Warning]s
+fn warning_errors(warnings: &ApiWarnings) -> Vec<ResponseError> {
+    warnings
+        .iter()
+        .map(|(module, warning)| ResponseError::Warning {
+            module: module.clone(),
+            info: warning.text.clone(),
+        })
+        .collect()
+}
+
+/// Parse the text of a response, failing if the API responded with an error
+///
+/// Warnings are logged, but don't fail the response
+pub(crate) fn checked_value(text: &str) -> Result<Value, ResponseError> {
+    let value: Value = serde_json::from_str(text)
+        .map_err(|err| ResponseError::Deserialisation(err.to_string()))?;
+
+    let envelope = Envelope::deserialize(&value)
+        .map_err(|err| ResponseError::Deserialisation(err.to_string()))?;
+
+    if let Some(error) = envelope.error {
+        return Err(ResponseError::Api {
+            code: error.code,
+            info: error.info,
+        });
+    }
+
+    for warning in warning_errors(&envelope.warnings) {
+        log::warn!("{warning}");
+    }
+
+    Ok(value)
+}
+
+/// Deserialise a value that was already checked for errors into a typed response
+pub(crate) fn from_value<T: DeserializeOwned>(value: Value) -> Result<T, ResponseError> {
+    serde_json::from_value(value).map_err(|err| ResponseError::Deserialisation(err.to_string()))
+}
+
+/// Parse the text of a response into a typed response, failing if the API responded with an error
+pub(crate) fn parse<T: DeserializeOwned>(text: &str) -> Result<T, ResponseError> {
+    from_value(checked_value(text)?)
+}
+
+/// Flags like `missing` are sent as `"missing": ""`, so only their presence matters
+fn present<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    IgnoredAny::deserialize(deserializer).map(|_| true)
+}
+
+fn is_false(flag: &bool) -> bool {
+    !flag
+}
+
+/// A response from `action=parse`
+///
+/// For example: <https://en.wikipedia.org/w/api.php?origin=*&action=parse&prop=wikitext|revid&format=json&page=Waffle>
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ParseResponse {
+    /// The parsed page
+    pub parse: ParsedPage,
+    /// The warnings sent along with the page
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub warnings: ApiWarnings,
+}
+
+impl ParseResponse {
+    /// Get the warnings sent along with the page as [ResponseError::Warning]s
+    pub fn warnings(&self) -> Vec<ResponseError> {
+        warning_errors(&self.warnings)
+    }
+}
+
+/// The page in a [ParseResponse]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ParsedPage {
+    /// The title of the page, after normalization
+    pub title: String,
+    /// The ID of the page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pageid: Option<u64>,
+    /// The ID of the parsed revision, only sent with `prop=revid`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revid: Option<u64>,
+    /// The wikitext of the page
+    pub wikitext: Wikitext,
+}
+
+/// The wikitext of a [ParsedPage], stored as `{"*": "wikitext"}` by the API
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Wikitext {
+    /// The wikitext itself
+    #[serde(rename = "*")]
+    pub text: String,
+}
+
+/// A response from `action=query`
+///
+/// For example: <https://en.wikipedia.org/w/api.php?action=query&format=json&prop=links|info&pllimit=500&origin=*&titles=Waffle>
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct QueryResponse {
+    /// The parameters needed to continue the query, if the results are incomplete
+    #[serde(rename = "continue", default, skip_serializing_if = "Option::is_none")]
+    pub continuation: Option<BTreeMap<String, String>>,
+    /// The results of the query
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<Query>,
+    /// The warnings sent along with the results
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub warnings: ApiWarnings,
+}
+
+impl QueryResponse {
+    /// Get the results of the query
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the response has no `query` field
+    pub fn query(&self) -> Result<&Query, ResponseError> {
+        self.query
+            .as_ref()
+            .ok_or(ResponseError::MissingField("query"))
+    }
+
+    /// Get the only page of a query for a single title
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the response has no `query.pages` field, or it is empty
+    pub fn first_page(&self) -> Result<&QueryPage, ResponseError> {
+        self.query()?
+            .pages()?
+            .values()
+            .next()
+            .ok_or(ResponseError::MissingField("query.pages"))
+    }
+
+    /// Get the warnings sent along with the results as [ResponseError::Warning]s
+    pub fn warnings(&self) -> Vec<ResponseError> {
+        warning_errors(&self.warnings)
+    }
+}
+
+/// The `query` field of a [QueryResponse], only the fields for what was requested are sent
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Query {
+    /// The titles the API normalized, like `belgian_waffle` to `Belgian waffle`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub normalized: Vec<TitleMapping>,
+    /// The redirects the API followed, only sent with `redirects=1`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redirects: Vec<TitleMapping>,
+    /// The requested pages keyed by page ID, sent for `titles=...`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pages: Option<BTreeMap<String, QueryPage>>,
+    /// The pages linking to the requested page, sent for `list=backlinks`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backlinks: Option<Vec<PageRef>>,
+    /// Random pages, sent for `list=random`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub random: Option<Vec<PageRef>>,
+}
+
+impl Query {
+    /// Get the requested pages keyed by page ID
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the query has no `pages` field
+    pub fn pages(&self) -> Result<&BTreeMap<String, QueryPage>, ResponseError> {
+        self.pages
+            .as_ref()
+            .ok_or(ResponseError::MissingField("query.pages"))
+    }
+
+    /// Get the pages linking to the requested page
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the query has no `backlinks` field
+    pub fn backlinks(&self) -> Result<&[PageRef], ResponseError> {
+        self.backlinks
+            .as_deref()
+            .ok_or(ResponseError::MissingField("query.backlinks"))
+    }
+
+    /// Get the random pages
+    ///
+    /// # Errors
+    ///
+    /// This method fails if the query has no `random` field
+    pub fn random(&self) -> Result<&[PageRef], ResponseError> {
+        self.random
+            .as_deref()
+            .ok_or(ResponseError::MissingField("query.random"))
+    }
+}
+
+/// A page in the `pages` of a [Query]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QueryPage {
+    /// The ID of the page, missing pages have none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pageid: Option<u64>,
+    /// The namespace of the page
+    #[serde(default)]
+    pub ns: i64,
+    /// The title of the page
+    pub title: String,
+    /// The page doesn't exist
+    #[serde(
+        default,
+        deserialize_with = "present",
+        skip_serializing_if = "is_false"
+    )]
+    pub missing: bool,
+    /// The title isn't a valid title
+    #[serde(
+        default,
+        deserialize_with = "present",
+        skip_serializing_if = "is_false"
+    )]
+    pub invalid: bool,
+    /// Why the title isn't valid, only sent along with `invalid`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invalidreason: Option<String>,
+    /// The ID of the latest revision, only sent with `prop=info`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lastrevid: Option<u64>,
+    /// The pages this page links to, only sent with `prop=links`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<PageRef>,
+    /// The plain text introduction of the page, only sent with `prop=extracts`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extract: Option<String>,
+    /// The short description of the page, only sent with `prop=description`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// A small image of the page, only sent with `prop=pageimages`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thumbnail: Option<Thumbnail>,
+    /// The same page on the wikipedias of other languages, only sent with `prop=langlinks`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub langlinks: Vec<LangLink>,
+}
+
+/// A link from a [QueryPage] to the same page in another language
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LangLink {
+    /// The language code of the wikipedia the page is on, like `de`
+    pub lang: String,
+    /// The title of the page on that wikipedia
+    #[serde(rename = "*")]
+    pub title: String,
+}
+
+/// The thumbnail of a [QueryPage]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Thumbnail {
+    /// The URL of the image
+    pub source: String,
+    /// The width of the image in pixels
+    #[serde(default)]
+    pub width: u32,
+    /// The height of the image in pixels
+    #[serde(default)]
+    pub height: u32,
+}
+
+/// A reference to a page in a list, like the links of a page
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PageRef {
+    /// The ID of the page, not sent for links
+    #[serde(alias = "id", default, skip_serializing_if = "Option::is_none")]
+    pub pageid: Option<u64>,
+    /// The namespace of the page
+    #[serde(default)]
+    pub ns: i64,
+    /// The title of the page
+    pub title: String,
+}
+
+/// A title the API replaced with another, like a redirect
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TitleMapping {
+    /// The title that was requested
+    pub from: String,
+    /// The title it was replaced with
+    pub to: String,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn api_errors_and_warnings() {
+        let error = r#"{"error":{"code":"missingtitle","info":"The page you specified doesn't exist.","*":"See the docs"},"servedby":"mw1"}"#;
+
+        match parse::<ParseResponse>(error) {
+            Err(ResponseError::Api { code, .. }) => assert_eq!(code, "missingtitle"),
+            other => panic!("Expected an API error, got {other:?}"),
+        }
+
+        let warned = r#"{"warnings":{"main":{"*":"Unrecognized parameter: foo."}},"query":{"pages":{"-1":{"ns":0,"title":"Nope","missing":""}}}}"#;
+
+        let response: QueryResponse = parse(warned).unwrap();
+
+        assert!(matches!(
+            &response.warnings()[..],
+            [ResponseError::Warning { module, .. }] if module == "main"
+        ));
+        assert!(response.first_page().unwrap().missing);
+        assert!(matches!(
+            response.query().unwrap().backlinks(),
+            Err(ResponseError::MissingField("query.backlinks"))
+        ));
+
+        let no_title = r#"{"parse":{"pageid":1,"wikitext":{"*":""}}}"#;
+
+        assert!(
+            matches!(parse::<ParseResponse>(no_title), Err(ResponseError::Deserialisation(err)) if err.contains("title"))
+        );
+    }
+}
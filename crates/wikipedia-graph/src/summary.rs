@@ -0,0 +1,67 @@
+use crate::QueryPage;
+use url::Url;
+
+/// A short summary of a page, telling what it is about without loading its body
+///
+/// Loaded with [crate::WikipediaPage::load_summary]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PageSummary {
+    extract: String,
+    description: Option<String>,
+    thumbnail: Option<Url>,
+}
+
+impl PageSummary {
+    /// Create a summary from the plain text introduction of a page
+    pub fn new(extract: impl Into<String>) -> Self {
+        Self {
+            extract: extract.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the short description of the page
+    pub fn with_description(self, description: impl Into<String>) -> Self {
+        Self {
+            description: Some(description.into()),
+            ..self
+        }
+    }
+
+    /// Sets the URL of the page's thumbnail image
+    pub fn with_thumbnail(self, thumbnail: Url) -> Self {
+        Self {
+            thumbnail: Some(thumbnail),
+            ..self
+        }
+    }
+
+    /// Create a summary from a page of a query for `prop=extracts|description|pageimages`
+    ///
+    /// Fields that weren't sent are left empty, thumbnails that aren't valid URLs are skipped
+    pub fn from_query_page(page: &QueryPage) -> Self {
+        Self {
+            extract: page.extract.clone().unwrap_or_default().trim().to_string(),
+            description: page.description.clone(),
+            thumbnail: page
+                .thumbnail
+                .as_ref()
+                .and_then(|thumbnail| Url::parse(&thumbnail.source).ok()),
+        }
+    }
+
+    /// The plain text introduction of the page, empty if the page has none
+    pub fn extract(&self) -> &str {
+        &self.extract
+    }
+
+    /// The short description of the page, like `Norwegian dessert` for `Multekrem`
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// The URL of a small image of the page, if it has one
+    pub fn thumbnail(&self) -> Option<&Url> {
+        self.thumbnail.as_ref()
+    }
+}
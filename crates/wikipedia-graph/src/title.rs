@@ -0,0 +1,192 @@
+use crate::{Namespace, WikiProject};
+use icu_normalizer::ComposingNormalizerBorrowed;
+use std::fmt::Display;
+
+/// The canonical form of a page title, so different spellings of the same title are equal
+///
+/// Titles are normalized the way MediaWiki does it:
+/// - Percent-encoded characters are decoded, `Caf%C3%A9` is `Café`
+/// - Underscores are spaces, and runs of spaces are a single space
+/// - Unicode is composed ([NFC](https://unicode.org/reports/tr15/))
+/// - Namespace prefixes are spelled canonically, `image:A.jpg` is `File:A.jpg`
+/// - The first letter of the page name is uppercase, `waffle` is `Waffle`, except on wikis where it isn't, see [Title::for_project]
+///
+/// # Example
+/// ```
+/// # use wikipedia_graph::Title;
+/// assert_eq!(Title::new("belgian_waffle"), Title::new("Belgian%20waffle"));
+/// assert_eq!(Title::new("category:desserts").as_str(), "Category:Desserts");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Title(String);
+
+impl Title {
+    /// Normalize a title, a pathinfo or a percent-encoded title
+    pub fn new(title: impl AsRef<str>) -> Self {
+        Self::normalize(title.as_ref(), true)
+    }
+
+    /// Normalize a title on a project, keeping the case of the first letter on projects that don't capitalize it
+    ///
+    /// # Example
+    /// ```
+    /// # use wikipedia_graph::{Title, WikiProject};
+    /// assert_eq!(Title::for_project("waffle", WikiProject::Wiktionary).as_str(), "waffle");
+    /// assert_eq!(Title::for_project("waffle", WikiProject::Wikipedia).as_str(), "Waffle");
+    /// ```
+    pub fn for_project(title: impl AsRef<str>, project: WikiProject) -> Self {
+        Self::normalize(title.as_ref(), project.capitalizes_titles())
+    }
+
+    fn normalize(title: &str, capitalize_first: bool) -> Self {
+        let decoded = percent_decode(title);
+
+        let composed = ComposingNormalizerBorrowed::new_nfc().normalize(&decoded);
+
+        let title = composed
+            .replace('_', " ")
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let title = title.trim_start_matches(':').trim_start();
+
+        let namespace = Namespace::of_title(title);
+
+        Self(match (&namespace, title.split_once(':')) {
+            (Namespace::Main, _) | (_, None) => page_name(title, capitalize_first),
+            // Other wikis decide how their titles are spelled
+            (Namespace::Interwiki(prefix), Some((_, name))) => format!("{prefix}:{}", name.trim()),
+            (namespace, Some((_, name))) => {
+                format!(
+                    "{}:{}",
+                    namespace.prefix(),
+                    page_name(name.trim(), capitalize_first)
+                )
+            }
+        })
+    }
+
+    /// The title with spaces, like `Belgian waffle`
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The title with underscores, as it is written in URLs, like `Belgian_waffle`
+    pub fn pathinfo(&self) -> String {
+        self.0.replace(' ', "_")
+    }
+
+    /// The namespace of the title
+    pub fn namespace(&self) -> Namespace {
+        Namespace::of_title(&self.0)
+    }
+}
+
+/// Decode the percent-encoded characters of a title, like `Caf%C3%A9` to `Café`
+///
+/// Unlike form decoding, `+` is kept as it is, so `C++` stays `C++`
+pub(crate) fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        let escaped = match bytes.get(index..index + 3) {
+            Some([b'%', high, low]) => std::str::from_utf8(&[*high, *low])
+                .ok()
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok()),
+            _ => None,
+        };
+
+        match escaped {
+            Some(byte) => {
+                decoded.push(byte);
+                index += 3;
+            }
+            None => {
+                decoded.push(bytes[index]);
+                index += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// The page name of a title, with an uppercase first letter if the wiki capitalizes titles
+fn page_name(name: &str, capitalize_first: bool) -> String {
+    match capitalize_first {
+        true => capitalize(name),
+        false => name.to_string(),
+    }
+}
+
+/// Uppercase the first letter of a title
+fn capitalize(title: &str) -> String {
+    let mut chars = title.chars();
+
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+impl Display for Title {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl AsRef<str> for Title {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for Title {
+    fn from(title: &str) -> Self {
+        Self::new(title)
+    }
+}
+
+impl From<String> for Title {
+    fn from(title: String) -> Self {
+        Self::new(title)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn normalization() {
+        let waffle = Title::new("Waffle");
+
+        for spelling in ["waffle", "Waffle_", " waffle ", "%57affle", ":Waffle"] {
+            assert_eq!(Title::new(spelling), waffle, "{spelling}");
+        }
+
+        assert_eq!(Title::new("belgian__waffle").as_str(), "Belgian waffle");
+        assert_eq!(Title::new("Belgian waffle").pathinfo(), "Belgian_waffle");
+
+        // 'é' as a single character and as 'e' with a combining accent
+        assert_eq!(Title::new("Caf\u{e9}"), Title::new("Cafe\u{301}"));
+        assert_eq!(Title::new("Caf%C3%A9"), Title::new("Café"));
+
+        // '+' is part of titles, not an encoded space
+        assert_eq!(Title::new("C++").as_str(), "C++");
+        assert_eq!(Title::new("C%2B%2B"), Title::new("C++"));
+        assert_ne!(Title::new("C++"), Title::new("C"));
+        assert_eq!(Title::new("100%_juice").as_str(), "100% juice");
+
+        assert_eq!(
+            Title::new("image:moltekrem.jpg").as_str(),
+            "File:Moltekrem.jpg"
+        );
+        assert_eq!(Title::new("fr:gaufre").as_str(), "fr:gaufre");
+        assert_eq!(Title::new("star Wars: andor").as_str(), "Star Wars: andor");
+        assert_eq!(Title::new("éclair").as_str(), "Éclair");
+    }
+}
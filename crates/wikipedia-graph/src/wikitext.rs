@@ -0,0 +1,392 @@
+use crate::{LinkPlacement, Namespace};
+
+/// Tags whose content is never parsed for links
+const SKIPPED_TAGS: [&str; 4] = ["nowiki", "ref", "pre", "math"];
+
+/// A wikilink like `[[Target#Anchor|Display]]`, found in the wikitext of a page
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WikiLink {
+    target: String,
+    display: Option<String>,
+    anchor: Option<String>,
+    namespace: Namespace,
+}
+
+impl WikiLink {
+    /// Find every wikilink in wikitext, in the order they appear
+    ///
+    /// Comments and the content of `<nowiki>`, `<ref>`, `<pre>` and `<math>` tags are skipped.
+    /// Links inside templates and inside the captions of other links, like `[[File:A.jpg|thumb|[[B]]]]`, are included
+    pub fn parse_all(wikitext: &str) -> Vec<WikiLink> {
+        Self::parse_all_located(wikitext)
+            .into_iter()
+            .map(|located| located.link)
+            .collect()
+    }
+
+    /// Find every wikilink in wikitext along with the section and part of the article they are in
+    pub(crate) fn parse_all_located(wikitext: &str) -> Vec<LocatedLink> {
+        let mut scanner = Scanner::default();
+
+        scanner.scan(wikitext, true);
+
+        scanner.links
+    }
+
+    /// Parse the inside of a wikilink, without the surrounding brackets
+    ///
+    /// Returns [None] if the target is empty, only an anchor or built from a template
+    pub fn parse(inner: &str) -> Option<WikiLink> {
+        let (target, display) = match inner.split_once('|') {
+            Some((target, display)) => (target, Some(display.trim().to_string())),
+            None => (inner, None),
+        };
+
+        // A leading colon links to a page instead of adding the page to a category or file
+        let target = target.trim().trim_start_matches(':').trim();
+
+        if target.contains(['{', '}', '<', '>', '[', ']']) {
+            return None;
+        }
+
+        let (target, anchor) = match target.split_once('#') {
+            Some((target, anchor)) => (target.trim(), Some(anchor.trim().to_string())),
+            None => (target, None),
+        };
+
+        if target.is_empty() {
+            return None;
+        }
+
+        Some(WikiLink {
+            target: target.to_string(),
+            display,
+            anchor,
+            namespace: Namespace::of_title(target),
+        })
+    }
+
+    /// The page the link leads to, including any namespace prefix, like `Category:Desserts`
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    /// The text shown instead of the target, like `Norwegian` in `[[Norwegian cuisine|Norwegian]]`
+    pub fn display(&self) -> Option<&str> {
+        self.display.as_deref()
+    }
+
+    /// The section of the page the link leads to, like `History` in `[[Waffle#History]]`
+    pub fn anchor(&self) -> Option<&str> {
+        self.anchor.as_deref()
+    }
+
+    /// The namespace of the target, like [Namespace::Category] or [Namespace::Interwiki] for `fr:`
+    ///
+    /// Prefixes that aren't known namespaces are part of the title, like in `Star Wars: Andor`
+    pub fn namespace(&self) -> &Namespace {
+        &self.namespace
+    }
+
+    /// Check if the link leads to an article
+    pub fn is_article(&self) -> bool {
+        self.namespace.is_main()
+    }
+}
+
+/// Find the end of the link that `text` starts with, as the index of its closing `]]`
+///
+/// Links and templates inside the link are skipped over
+fn link_end(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+
+    // The brackets opened so far, `]]` inside a template doesn't close a link and `}}` inside a link doesn't close a template
+    let mut open = Vec::new();
+    let mut index = 0;
+
+    while index + 1 < bytes.len() {
+        match (&bytes[index..index + 2], open.last()) {
+            (b"[[" | b"{{", _) => open.push(bytes[index]),
+            (b"]]", Some(b'[')) | (b"}}", Some(b'{')) => {
+                open.pop();
+
+                if open.is_empty() {
+                    return Some(index);
+                }
+            }
+            _ => {
+                index += 1;
+                continue;
+            }
+        }
+
+        index += 2;
+    }
+
+    None
+}
+
+/// Find the first occurrence of an ASCII `needle` in `text`, ignoring ASCII case
+fn find_ignore_ascii_case(text: &str, needle: &str) -> Option<usize> {
+    text.as_bytes()
+        .windows(needle.len())
+        .position(|window| window.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+/// Get the length of the tag `text` starts with, including its content, if it is a tag that is skipped
+fn skipped_tag_length(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+
+    // Compared as bytes, so the text after a short tag name may start with any character
+    let name = SKIPPED_TAGS.iter().find(|name| {
+        bytes.first() == Some(&b'<')
+            && bytes
+                .get(1..=name.len())
+                .is_some_and(|tag| tag.eq_ignore_ascii_case(name.as_bytes()))
+            && bytes
+                .get(name.len() + 1)
+                .is_some_and(|next| matches!(next, b' ' | b'>' | b'/' | b'\n' | b'\t'))
+    })?;
+
+    let open_end = text.find('>')? + 1;
+
+    if text[..open_end].ends_with("/>") {
+        return Some(open_end);
+    }
+
+    let close = format!("</{name}");
+
+    let close_start = find_ignore_ascii_case(&text[open_end..], &close)? + open_end;
+
+    Some(
+        text[close_start..]
+            .find('>')
+            .map_or(text.len(), |end| close_start + end + 1),
+    )
+}
+
+/// Get the level and title of a heading line like `== History ==`, or [None] if the line isn't a heading
+fn heading(line: &str) -> Option<(usize, String)> {
+    let line = line.trim_end();
+
+    let opening = line.len() - line.trim_start_matches('=').len();
+    let closing = line.len() - line.trim_end_matches('=').len();
+
+    if opening < 2 || closing < 2 {
+        return None;
+    }
+
+    Some(line.trim_matches('=').trim())
+        .filter(|title| !title.is_empty())
+        .map(|title| (opening.min(closing), title.to_string()))
+}
+
+/// A wikilink along with where it was found
+pub(crate) struct LocatedLink {
+    pub(crate) link: WikiLink,
+    pub(crate) sections: Vec<String>,
+    pub(crate) placement: LinkPlacement,
+}
+
+/// The state of a scan through wikitext
+#[derive(Default)]
+struct Scanner {
+    links: Vec<LocatedLink>,
+    /// The levels and headings of the current section and the sections it is part of, outermost first
+    sections: Vec<(usize, String)>,
+    /// The names of the templates the scan is inside of, outermost first
+    templates: Vec<String>,
+}
+
+impl Scanner {
+    /// Decide which part of the article the scan is in
+    ///
+    /// Only templates named like `Infobox food` and `Navbox` are recognized as infoboxes and navboxes
+    fn placement(&self) -> LinkPlacement {
+        let inside = |name: &str| {
+            self.templates
+                .iter()
+                .any(|template| template.to_lowercase().contains(name))
+        };
+
+        if inside("infobox") {
+            LinkPlacement::Infobox
+        } else if inside("navbox") {
+            LinkPlacement::Navbox
+        } else if self.sections.is_empty() {
+            LinkPlacement::Lead
+        } else {
+            LinkPlacement::Body
+        }
+    }
+
+    /// Scan wikitext for links, headings are only recognized at the top level
+    fn scan(&mut self, text: &str, top_level: bool) {
+        let mut index = 0;
+
+        while index < text.len() {
+            let rest = &text[index..];
+
+            if rest.starts_with("<!--") {
+                index += rest.find("-->").map_or(rest.len(), |end| end + 3);
+                continue;
+            }
+
+            if rest.starts_with('<')
+                && let Some(length) = skipped_tag_length(rest)
+            {
+                index += length;
+                continue;
+            }
+
+            if top_level
+                && self.templates.is_empty()
+                && (index == 0 || text[..index].ends_with('\n'))
+                && let Some((level, title)) = heading(rest.lines().next().unwrap_or_default())
+            {
+                while self
+                    .sections
+                    .last()
+                    .is_some_and(|(parent, _)| *parent >= level)
+                {
+                    self.sections.pop();
+                }
+
+                self.sections.push((level, title));
+            }
+
+            if let Some(template) = rest.strip_prefix("{{") {
+                let name = template
+                    .split(['|', '}', '\n'])
+                    .next()
+                    .unwrap_or_default()
+                    .trim();
+
+                self.templates.push(name.to_string());
+
+                index += 2;
+                continue;
+            }
+
+            if rest.starts_with("}}") && self.templates.pop().is_some() {
+                index += 2;
+                continue;
+            }
+
+            if rest.starts_with("[[")
+                && let Some(end) = link_end(rest)
+            {
+                let inner = &rest[2..end];
+
+                if let Some(link) = WikiLink::parse(inner) {
+                    self.links.push(LocatedLink {
+                        link,
+                        sections: self
+                            .sections
+                            .iter()
+                            .map(|(_, title)| title.clone())
+                            .collect(),
+                        placement: self.placement(),
+                    });
+                }
+
+                // Captions may contain links of their own
+                if let Some((_, caption)) = inner.split_once('|') {
+                    self.scan(caption, false);
+                }
+
+                index += end + 2;
+                continue;
+            }
+
+            index += rest.chars().next().map_or(1, char::len_utf8);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn targets(wikitext: &str) -> Vec<String> {
+        WikiLink::parse_all(wikitext)
+            .into_iter()
+            .map(|link| link.target)
+            .collect()
+    }
+
+    #[test]
+    fn link_parts() {
+        let link = WikiLink::parse(" Waffle#History | waffles ").unwrap();
+
+        assert_eq!(link.target(), "Waffle");
+        assert_eq!(link.anchor(), Some("History"));
+        assert_eq!(link.display(), Some("waffles"));
+        assert!(link.is_article());
+
+        assert_eq!(
+            WikiLink::parse(":Category:Desserts").unwrap().namespace(),
+            &Namespace::Category
+        );
+        assert_eq!(
+            WikiLink::parse("fr:Gaufre").unwrap().namespace(),
+            &Namespace::Interwiki("fr".to_string())
+        );
+        assert!(WikiLink::parse("Star Wars: Andor").unwrap().is_article());
+        assert!(WikiLink::parse("#Section").is_none());
+        assert!(WikiLink::parse("{{PAGENAME}}").is_none());
+    }
+
+    #[test]
+    fn skipped_content() {
+        let wikitext = "[[A]]<!-- [[B]] --><nowiki>[[C]]</nowiki><ref name=\"x\">[[D]]</ref>\
+            <ref name=\"y\" />[[E]]<REF>[[F]]</REF><references/>[[G]]";
+
+        assert_eq!(targets(wikitext), ["A", "E", "G"]);
+
+        let wikitext = "[[A]]<ref>Ελληνικά [[B]]</ref>[[C]]<pre>日本語の[[D]]</PRE>[[E]]";
+
+        assert_eq!(targets(wikitext), ["A", "C", "E"]);
+    }
+
+    #[test]
+    fn nested_links() {
+        let wikitext = "[[File:A.jpg|thumb|With [[B|b]] and {{lang|no|[[C]]}}]] {{Outer|{{Inner|[[D]]}}}} [[E|{{small|]]}}]]";
+
+        assert_eq!(targets(wikitext), ["File:A.jpg", "B", "C", "D", "E"]);
+    }
+
+    #[test]
+    fn link_locations() {
+        let wikitext = "{{Infobox food|country=[[Norway]]}}\n'''Multekrem''' is a [[dessert]].\n\
+            == Serving ==\nWith [[krumkake]] {{lang|no|[[Cloudberry]]}}\n=== Notes ===\n\
+            {{Navbox|list=[[Riskrem]]}}\n==Reception==\n[[Norway]]";
+
+        let located: Vec<(String, String, LinkPlacement)> = WikiLink::parse_all_located(wikitext)
+            .into_iter()
+            .map(|located| {
+                (
+                    located.link.target,
+                    located.sections.join("/"),
+                    located.placement,
+                )
+            })
+            .collect();
+
+        let expected = |target: &str, sections: &str, placement| {
+            (target.to_string(), sections.to_string(), placement)
+        };
+
+        assert_eq!(
+            located,
+            [
+                expected("Norway", "", LinkPlacement::Infobox),
+                expected("dessert", "", LinkPlacement::Lead),
+                expected("krumkake", "Serving", LinkPlacement::Body),
+                expected("Cloudberry", "Serving", LinkPlacement::Body),
+                expected("Riskrem", "Serving/Notes", LinkPlacement::Navbox),
+                expected("Norway", "Reception", LinkPlacement::Body),
+            ]
+        );
+    }
+}
@@ -0,0 +1,1187 @@
+#[path = "common.rs"]
+mod common;
+
+#[cfg(feature = "client")]
+mod fixture {
+    use crate::common::{self, multekrem_page_text};
+    use ehttp::Request;
+    use pretty_assertions::assert_eq;
+    use std::sync::mpsc::channel;
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, Instant};
+    use wikipedia_graph::{
+        BackoffPolicy, FixtureTransport, HttpError, HttpTransport, MemoryCache, RateLimit,
+        TransportCallback, Url, WikiLanguage, WikiProject, WikipediaBody, WikipediaClient,
+        WikipediaClientConfig, WikipediaPage, WikipediaUrlType,
+    };
+
+    const MULTEKREM_URL: &str = "https://en.wikipedia.org/w/api.php?origin=*&action=parse&prop=wikitext|revid&format=json&page=Multekrem";
+
+    const MOLTEKREM_URL: &str = "https://en.wikipedia.org/w/api.php?origin=*&action=parse&prop=wikitext|revid&format=json&page=Moltekrem";
+
+    const MOLTEKREM_REDIRECT: &str = r##"{"parse":{"title":"Moltekrem","pageid":1,"wikitext":{"*":"#REDIRECT [[Multekrem]]"}}}"##;
+
+    /// Serves fixtures from another thread, like a real backend would
+    #[derive(Debug)]
+    struct ThreadedTransport(FixtureTransport);
+
+    impl HttpTransport for ThreadedTransport {
+        fn fetch(&self, request: Request, callback: TransportCallback) {
+            let transport = self.0.clone();
+
+            std::thread::spawn(move || transport.fetch(request, callback));
+        }
+    }
+
+    /// Never responds to any request
+    #[derive(Debug)]
+    struct SilentTransport;
+
+    impl HttpTransport for SilentTransport {
+        fn fetch(&self, _: Request, _: TransportCallback) {}
+    }
+
+    /// Holds on to every request until the test responds to it
+    #[derive(Clone, Default)]
+    struct ManualTransport {
+        pending: Arc<Mutex<Vec<(Request, TransportCallback)>>>,
+    }
+
+    impl ManualTransport {
+        fn pending(&self) -> usize {
+            self.pending.lock().unwrap().len()
+        }
+
+        fn respond_to_first(&self, transport: &FixtureTransport) {
+            let (request, callback) = self.pending.lock().unwrap().remove(0);
+
+            transport.fetch(request, callback);
+        }
+    }
+
+    impl std::fmt::Debug for ManualTransport {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "ManualTransport({} pending)", self.pending())
+        }
+    }
+
+    impl HttpTransport for ManualTransport {
+        fn fetch(&self, request: Request, callback: TransportCallback) {
+            self.pending.lock().unwrap().push((request, callback));
+        }
+    }
+
+    const RANDOM_URL: &str = "https://en.wikipedia.org/w/api.php?action=query&format=json&list=random&rnnamespace=0&rnlimit=1&origin=*";
+
+    const RANDOM_BODY: &str =
+        r#"{"batchcomplete":"","query":{"random":[{"id":5230098,"ns":0,"title":"Multekrem"}]}}"#;
+
+    fn client(transport: FixtureTransport) -> WikipediaClient {
+        WikipediaClient::from_config(WikipediaClientConfig::default().transport(transport))
+    }
+
+    fn get_blocking(client: &WikipediaClient, pathinfo: &str) -> Result<WikipediaBody, HttpError> {
+        let (sender, reciever) = channel();
+
+        client
+            .get(pathinfo, move |response| {
+                sender
+                    .send(response)
+                    .expect("Failed to send response to test thread")
+            })
+            .expect("Language 'en' is invalid");
+
+        reciever.recv().expect("Callback was never called")
+    }
+
+    #[test]
+    fn get_page() {
+        let client =
+            client(FixtureTransport::new().with_response(MULTEKREM_URL, multekrem_page_text()));
+
+        let body = get_blocking(&client, "Multekrem").expect("Failed to get fixture page");
+
+        assert_eq!(body.get_pathinfo().unwrap(), "Multekrem");
+
+        assert_eq!(
+            body.get_linked_pages()
+                .expect("Fixture body is malformed")
+                .collect::<Vec<_>>(),
+            common::multekrem_pages_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn get_follows_redirect() {
+        let transport = FixtureTransport::new()
+            .with_response(MOLTEKREM_URL, MOLTEKREM_REDIRECT)
+            .with_response(MULTEKREM_URL, multekrem_page_text());
+
+        let client = client(transport.clone());
+
+        let body = get_blocking(&client, "Moltekrem").expect("Failed to follow redirect");
+
+        assert_eq!(body.get_pathinfo().unwrap(), "Multekrem");
+        assert_eq!(transport.requests(), vec![MOLTEKREM_URL, MULTEKREM_URL]);
+    }
+
+    #[test]
+    fn get_missing_page() {
+        let client = client(FixtureTransport::new());
+
+        let err = get_blocking(&client, "Multekrem").unwrap_err();
+
+        assert!(matches!(err.kind(), HttpError::PageNotFound));
+        assert_eq!(err.url(), Some(MULTEKREM_URL));
+    }
+
+    #[test]
+    fn unconditional_not_modified() {
+        let client = client(FixtureTransport::new().with_headers(MULTEKREM_URL, 304, &[], ""));
+
+        let err = get_blocking(&client, "Multekrem").unwrap_err();
+
+        assert!(matches!(err.kind(), HttpError::Unknown(304)));
+    }
+
+    #[test]
+    fn random_page() {
+        let client = client(FixtureTransport::new().with_response(RANDOM_URL, RANDOM_BODY));
+
+        let (sender, reciever) = channel::<Result<WikipediaPage, HttpError>>();
+
+        client
+            .random_page(move |response| sender.send(response).unwrap())
+            .unwrap();
+
+        assert_eq!(reciever.recv().unwrap().unwrap().title(), "Multekrem");
+    }
+
+    #[test]
+    fn api_base() {
+        let (sender, reciever) = channel();
+
+        client(
+            FixtureTransport::new()
+                .with_response("https://en.wikipedia.org/w/api.php?origin=*", "{}"),
+        )
+        .get_api_base(move |response| sender.send(response).unwrap());
+
+        assert!(reciever.recv().unwrap().is_ok());
+
+        let (sender, reciever) = channel();
+
+        WikipediaClient::from_config(WikipediaClientConfig::default().max_retries(0).transport(
+            FixtureTransport::new().with_status(
+                "https://en.wikipedia.org/w/api.php?origin=*",
+                503,
+                "",
+            ),
+        ))
+        .get_api_base(move |response| sender.send(response).unwrap());
+
+        assert!(matches!(
+            reciever.recv().unwrap().unwrap_err().kind(),
+            HttpError::Unknown(503)
+        ));
+    }
+
+    #[test]
+    fn get_async() {
+        let transport = FixtureTransport::new()
+            .with_response(MOLTEKREM_URL, MOLTEKREM_REDIRECT)
+            .with_response(MULTEKREM_URL, multekrem_page_text());
+
+        let client = WikipediaClient::from_config(
+            WikipediaClientConfig::default().transport(ThreadedTransport(transport)),
+        );
+
+        let body = pollster::block_on(client.get_async("Moltekrem")).expect("Failed to get page");
+
+        assert_eq!(body.get_pathinfo().unwrap(), "Multekrem");
+
+        // The callback API runs the same future
+        assert_eq!(
+            get_blocking(&client, "Moltekrem")
+                .unwrap()
+                .get_pathinfo()
+                .unwrap(),
+            "Multekrem"
+        );
+    }
+
+    #[test]
+    fn page_async() {
+        let transport = FixtureTransport::new()
+            .with_response(RANDOM_URL, RANDOM_BODY)
+            .with_response(MULTEKREM_URL, multekrem_page_text());
+
+        let client = client(transport);
+
+        let page = pollster::block_on(WikipediaPage::random_async(&client)).unwrap();
+
+        assert!(!page.is_page_text_loaded());
+
+        let page = pollster::block_on(page.load_page_text_async(&client)).unwrap();
+
+        assert!(page.is_page_text_loaded());
+        assert_eq!(page.title(), "Multekrem");
+
+        assert!(pollster::block_on(client.get_api_base_async()).is_err());
+    }
+
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn blocking_client() {
+        use wikipedia_graph::BlockingWikipediaClient;
+
+        let transport = FixtureTransport::new()
+            .with_response(RANDOM_URL, RANDOM_BODY)
+            .with_response(MOLTEKREM_URL, MOLTEKREM_REDIRECT)
+            .with_response(MULTEKREM_URL, multekrem_page_text());
+
+        let client = BlockingWikipediaClient::from_config(
+            WikipediaClientConfig::default().transport(ThreadedTransport(transport)),
+        );
+
+        let page = client.random_page().unwrap();
+
+        assert_eq!(client.load_page_text(&page).unwrap().title(), "Multekrem");
+        assert_eq!(
+            client.get("Moltekrem").unwrap().get_pathinfo().unwrap(),
+            "Multekrem"
+        );
+        assert!(matches!(
+            client.get_api_base().unwrap_err().kind(),
+            HttpError::PageNotFound
+        ));
+    }
+
+    fn retrying_client(
+        transport: impl HttpTransport + 'static,
+        max_retries: u32,
+    ) -> WikipediaClient {
+        WikipediaClient::from_config(
+            WikipediaClientConfig::default()
+                .max_retries(max_retries)
+                .backoff(BackoffPolicy::Constant(Duration::from_millis(1)))
+                .transport(transport),
+        )
+    }
+
+    #[test]
+    fn retry_server_errors() {
+        let transport = FixtureTransport::new()
+            .with_status(MULTEKREM_URL, 503, "")
+            .with_status(MULTEKREM_URL, 429, "")
+            .with_response(MULTEKREM_URL, multekrem_page_text());
+
+        let body = pollster::block_on(retrying_client(transport.clone(), 2).get_async("Multekrem"));
+
+        assert_eq!(body.unwrap().get_pathinfo().unwrap(), "Multekrem");
+        assert_eq!(transport.requests().len(), 3);
+    }
+
+    #[test]
+    fn retries_exhausted() {
+        let transport = FixtureTransport::new().with_status(MULTEKREM_URL, 502, "");
+
+        let response =
+            pollster::block_on(retrying_client(transport.clone(), 2).get_async("Multekrem"));
+
+        match response.as_ref().map_err(HttpError::kind) {
+            Err(HttpError::AttemptsExhausted { attempts, source }) => {
+                assert_eq!(*attempts, 3);
+                assert!(matches!(**source, HttpError::Unknown(502)));
+            }
+            other => panic!("Expected the retries to be exhausted, got {other:?}"),
+        }
+
+        assert_eq!(transport.requests().len(), 3);
+
+        // Not found isn't worth retrying
+        let transport = FixtureTransport::new();
+
+        let response =
+            pollster::block_on(retrying_client(transport.clone(), 2).get_async("Multekrem"));
+
+        assert!(matches!(
+            response.unwrap_err().kind(),
+            HttpError::PageNotFound
+        ));
+        assert_eq!(transport.requests().len(), 1);
+
+        // Neither is it after a retry
+        let transport = FixtureTransport::new()
+            .with_status(MULTEKREM_URL, 429, "")
+            .with_status(MULTEKREM_URL, 404, "");
+
+        let response =
+            pollster::block_on(retrying_client(transport.clone(), 2).get_async("Multekrem"));
+
+        assert!(matches!(
+            response.unwrap_err().kind(),
+            HttpError::PageNotFound
+        ));
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    fn api_error_body(code: &str) -> String {
+        format!(
+            r#"{{"error":{{"code":"{code}","info":"Something about {code}"}},"servedby":"mw1"}}"#
+        )
+    }
+
+    #[test]
+    fn api_errors() {
+        let transport = FixtureTransport::new()
+            .with_response(MULTEKREM_URL, api_error_body("ratelimited"))
+            .with_response(MULTEKREM_URL, multekrem_page_text())
+            .with_response(raw_url("Nope"), api_error_body("missingtitle"))
+            .with_response(raw_url("Bad"), api_error_body("invalidtitle"))
+            .with_response(raw_url("Odd"), api_error_body("badvalue"));
+
+        let client = retrying_client(transport.clone(), 2);
+
+        assert!(pollster::block_on(client.get_async("Multekrem")).is_ok());
+        assert_eq!(transport.requests().len(), 2);
+
+        let err = pollster::block_on(client.get_async("Nope")).unwrap_err();
+
+        assert!(matches!(err.kind(), HttpError::MissingTitle));
+        assert_eq!(err.url(), Some(raw_url("Nope").as_str()));
+
+        assert!(matches!(
+            pollster::block_on(client.get_async("Bad")).unwrap_err().kind(),
+            HttpError::InvalidTitle(info) if info == "Something about invalidtitle"
+        ));
+        assert!(matches!(
+            pollster::block_on(client.get_async("Odd")).unwrap_err().kind(),
+            HttpError::ApiError { code, .. } if code == "badvalue"
+        ));
+
+        // The links API marks missing pages instead of sending an error
+        let links_url = "https://en.wikipedia.org/w/api.php?action=query&format=json&prop=links|info&pllimit=500&origin=*&titles=Nope";
+
+        let links = WikipediaClient::from_config(
+            WikipediaClientConfig::default()
+                .url_type(WikipediaUrlType::LinksApi)
+                .transport(FixtureTransport::new().with_response(
+                    links_url,
+                    r#"{"batchcomplete":"","query":{"pages":{"-1":{"ns":0,"title":"Nope","missing":""}}}}"#,
+                )),
+        );
+
+        let err = pollster::block_on(links.get_async("Nope")).unwrap_err();
+
+        assert!(matches!(err.kind(), HttpError::MissingTitle));
+        assert_eq!(err.url(), Some(links_url));
+    }
+
+    #[test]
+    fn retry_after_header() {
+        let transport = FixtureTransport::new()
+            .with_headers(MULTEKREM_URL, 429, &[("Retry-After", "0")], "")
+            .with_response(MULTEKREM_URL, multekrem_page_text());
+
+        let client = WikipediaClient::from_config(
+            WikipediaClientConfig::default()
+                .backoff(BackoffPolicy::Constant(Duration::from_secs(600)))
+                .transport(transport),
+        );
+
+        let start = Instant::now();
+
+        assert!(pollster::block_on(client.get_async("Multekrem")).is_ok());
+        assert!(start.elapsed() < Duration::from_secs(60));
+
+        // The header is never waited on longer than the backoff allows
+        let transport = FixtureTransport::new()
+            .with_headers(MULTEKREM_URL, 503, &[("Retry-After", "3600")], "")
+            .with_response(MULTEKREM_URL, multekrem_page_text());
+
+        let start = Instant::now();
+
+        assert!(pollster::block_on(retrying_client(transport, 1).get_async("Multekrem")).is_ok());
+        assert!(start.elapsed() < Duration::from_secs(60));
+    }
+
+    #[test]
+    fn timeout() {
+        let client = WikipediaClient::from_config(
+            WikipediaClientConfig::default()
+                .timeout(Some(Duration::from_millis(10)))
+                .max_retries(0)
+                .transport(SilentTransport),
+        );
+
+        assert!(matches!(
+            pollster::block_on(client.get_async("Multekrem"))
+                .unwrap_err()
+                .kind(),
+            HttpError::Timeout
+        ));
+
+        let client = WikipediaClient::from_config(
+            WikipediaClientConfig::default()
+                .timeout(Some(Duration::from_millis(10)))
+                .max_retries(1)
+                .backoff(BackoffPolicy::Constant(Duration::from_millis(1)))
+                .transport(SilentTransport),
+        );
+
+        assert!(matches!(
+            pollster::block_on(client.get_async("Multekrem"))
+                .unwrap_err()
+                .kind(),
+            HttpError::AttemptsExhausted { attempts: 2, .. }
+        ));
+    }
+
+    #[test]
+    fn max_in_flight() {
+        let manual = ManualTransport::default();
+
+        let client = WikipediaClient::from_config(
+            WikipediaClientConfig::default()
+                .max_in_flight(Some(2))
+                .rate_limit(None)
+                .transport(manual.clone()),
+        );
+
+        let (sender, reciever) = channel();
+
+        for _ in 0..5 {
+            let sender = sender.clone();
+
+            client
+                .get("Multekrem", move |response| sender.send(response).unwrap())
+                .unwrap();
+        }
+
+        assert_eq!(manual.pending(), 2);
+
+        let fixture = FixtureTransport::new().with_response(MULTEKREM_URL, multekrem_page_text());
+
+        for remaining in (0..5).rev() {
+            manual.respond_to_first(&fixture);
+
+            assert!(reciever.recv().unwrap().is_ok());
+            assert_eq!(manual.pending(), remaining.min(2));
+        }
+
+        // A request that timed out still counts until the transport is done with it
+        let manual = ManualTransport::default();
+
+        let client = WikipediaClient::from_config(
+            WikipediaClientConfig::default()
+                .max_in_flight(Some(1))
+                .rate_limit(None)
+                .timeout(Some(Duration::from_millis(10)))
+                .max_retries(0)
+                .transport(manual.clone()),
+        );
+
+        for _ in 0..2 {
+            let sender = sender.clone();
+
+            client
+                .get("Multekrem", move |response| sender.send(response).unwrap())
+                .unwrap();
+        }
+
+        assert!(matches!(
+            reciever.recv().unwrap().unwrap_err().kind(),
+            HttpError::Timeout
+        ));
+        assert_eq!(manual.pending(), 1);
+
+        manual.respond_to_first(&fixture);
+
+        assert_eq!(manual.pending(), 1);
+        assert!(matches!(
+            reciever.recv().unwrap().unwrap_err().kind(),
+            HttpError::Timeout
+        ));
+    }
+
+    #[test]
+    fn rate_limit() {
+        let transport = FixtureTransport::new().with_response(MULTEKREM_URL, multekrem_page_text());
+
+        let client = WikipediaClient::from_config(
+            WikipediaClientConfig::default()
+                .rate_limit(Some(RateLimit::new(50.0, 1)))
+                .transport(transport.clone()),
+        );
+
+        let (sender, reciever) = channel();
+
+        let start = Instant::now();
+
+        for _ in 0..5 {
+            let sender = sender.clone();
+
+            client
+                .get("Multekrem", move |response| sender.send(response).unwrap())
+                .unwrap();
+        }
+
+        // Only the burst is allowed through straight away
+        assert_eq!(transport.requests().len(), 1);
+
+        for _ in 0..5 {
+            assert!(reciever.recv().unwrap().is_ok());
+        }
+
+        assert!(start.elapsed() >= Duration::from_millis(75));
+    }
+
+    fn redirect_body(from: &str, to: &str) -> String {
+        format!(
+            r##"{{"parse":{{"title":"{from}","pageid":1,"wikitext":{{"*":"#REDIRECT [[{to}]]"}}}}}}"##
+        )
+    }
+
+    fn raw_url(title: &str) -> String {
+        format!(
+            "https://en.wikipedia.org/w/api.php?origin=*&action=parse&prop=wikitext|revid&format=json&page={title}"
+        )
+    }
+
+    #[test]
+    fn redirect_chain() {
+        let transport = FixtureTransport::new()
+            .with_response(raw_url("USA"), redirect_body("USA", "Moltekrem"))
+            .with_response(MOLTEKREM_URL, MOLTEKREM_REDIRECT)
+            .with_response(MULTEKREM_URL, multekrem_page_text());
+
+        let page = pollster::block_on(
+            WikipediaPage::from_title("USA").load_page_text_async(&client(transport)),
+        )
+        .unwrap();
+
+        assert_eq!(page.title(), "Multekrem");
+        assert_eq!(page.redirects(), ["USA", "Moltekrem"]);
+        assert!(page.is_same_page(&WikipediaPage::from_title("USA")));
+        assert!(WikipediaPage::from_title("Moltekrem").is_same_page(&page));
+    }
+
+    #[test]
+    fn redirect_loop() {
+        let transport = FixtureTransport::new()
+            .with_response(raw_url("A"), redirect_body("A", "B"))
+            .with_response(raw_url("B"), redirect_body("B", "A"));
+
+        let err = pollster::block_on(client(transport.clone()).get_async("A")).unwrap_err();
+
+        match err.kind() {
+            HttpError::RedirectLoop(titles) => assert_eq!(titles, &["A", "B"]),
+            other => panic!("Expected a redirect loop, got {other:?}"),
+        }
+
+        assert_eq!(err.url(), Some(raw_url("B").as_str()));
+
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    #[test]
+    fn too_many_redirects() {
+        let transport = (0..5).fold(FixtureTransport::new(), |transport, index| {
+            transport.with_response(
+                raw_url(&format!("Page_{index}")),
+                redirect_body(&format!("Page {index}"), &format!("Page {}", index + 1)),
+            )
+        });
+
+        let client = WikipediaClient::from_config(
+            WikipediaClientConfig::default()
+                .max_redirects(3)
+                .transport(transport.clone()),
+        );
+
+        assert!(matches!(
+            pollster::block_on(client.get_async("Page_0"))
+                .unwrap_err()
+                .kind(),
+            HttpError::TooManyRedirects
+        ));
+        assert_eq!(transport.requests().len(), 4);
+    }
+
+    fn batch_url(titles: &[String]) -> String {
+        let mut url = Url::parse("https://en.wikipedia.org/w/api.php?action=query&format=json&prop=links|info&pllimit=500&redirects=1&origin=*").unwrap();
+
+        url.query_pairs_mut()
+            .append_pair("titles", titles.join("|").as_str());
+
+        url.to_string()
+    }
+
+    /// A links response for every title, with 'Page 7' missing and 'Page 3' redirecting to 'Multekrem'
+    fn batch_body(titles: &[String]) -> String {
+        let pages = titles
+            .iter()
+            .enumerate()
+            .map(|(index, title)| match title.as_str() {
+                "Page 7" => format!(r#""-{index}":{{"ns":0,"title":"{title}","missing":""}}"#),
+                "Page 3" => format!(
+                    r#""{index}":{{"pageid":{index},"ns":0,"title":"Multekrem","links":[{{"ns":0,"title":"Dessert"}}]}}"#
+                ),
+                _ => format!(
+                    r#""{index}":{{"pageid":{index},"ns":0,"title":"{title}","links":[{{"ns":0,"title":"Link of {title}"}}]}}"#
+                ),
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let redirects = if titles.iter().any(|title| title == "Page 3") {
+            r#""redirects":[{"from":"Page 3","to":"Multekrem"}],"#
+        } else {
+            ""
+        };
+
+        format!(r#"{{"batchcomplete":"","query":{{{redirects}"pages":{{{pages}}}}}}}"#)
+    }
+
+    #[test]
+    fn get_many() {
+        let titles: Vec<String> = (0..120).map(|index| format!("Page {index}")).collect();
+
+        let transport = titles
+            .chunks(wikipedia_graph::MAX_BATCH_TITLES)
+            .fold(FixtureTransport::new(), |transport, batch| {
+                transport.with_response(batch_url(batch), batch_body(batch))
+            });
+
+        let client = client(transport.clone());
+
+        let results = pollster::block_on(client.get_many_async(&titles));
+
+        assert_eq!(transport.requests().len(), 3);
+        assert_eq!(results.len(), titles.len());
+
+        for (index, (title, result)) in results.iter().enumerate() {
+            assert_eq!(title, &titles[index]);
+
+            match index {
+                7 => {
+                    let err = result.as_ref().unwrap_err();
+
+                    assert!(matches!(err.kind(), HttpError::MissingTitle));
+                    assert_eq!(err.url(), Some(batch_url(&titles[..50]).as_str()));
+                }
+                3 => assert_eq!(
+                    result.as_ref().unwrap().get_pathinfo().unwrap(),
+                    "Multekrem"
+                ),
+                _ => assert_eq!(
+                    result
+                        .as_ref()
+                        .unwrap()
+                        .get_linked_pages()
+                        .unwrap()
+                        .next()
+                        .unwrap()
+                        .title(),
+                    format!("Link of {title}")
+                ),
+            }
+        }
+
+        let transport = FixtureTransport::new()
+            .with_response(batch_url(&titles[..10]), batch_body(&titles[..10]));
+
+        let pages: Vec<WikipediaPage> =
+            titles[..10].iter().map(WikipediaPage::from_title).collect();
+
+        let loaded = pollster::block_on(WikipediaPage::load_many_async(
+            &pages,
+            &self::client(transport),
+        ));
+
+        let redirected = loaded[3].as_ref().unwrap();
+
+        assert_eq!(redirected.title(), "Multekrem");
+        assert_eq!(redirected.redirects(), ["Page 3"]);
+        assert!(loaded[0].as_ref().unwrap().is_page_text_loaded());
+    }
+
+    #[test]
+    fn links_continue() {
+        let titles = [String::from("A"), String::from("B")];
+
+        let mut continued_url = Url::parse(&batch_url(&titles)).unwrap();
+
+        continued_url
+            .query_pairs_mut()
+            .append_pair("continue", "||")
+            .append_pair("plcontinue", "1|0|A3");
+
+        let transport = FixtureTransport::new()
+            .with_response(
+                batch_url(&titles),
+                r#"{"continue":{"plcontinue":"1|0|A3","continue":"||"},"query":{"pages":{"1":{"pageid":1,"ns":0,"title":"A","links":[{"ns":0,"title":"A1"},{"ns":0,"title":"A2"}]},"2":{"pageid":2,"ns":0,"title":"B"}}}}"#,
+            )
+            .with_response(
+                continued_url.as_str(),
+                r#"{"batchcomplete":"","query":{"pages":{"1":{"pageid":1,"ns":0,"title":"A","links":[{"ns":0,"title":"A3"}]},"2":{"pageid":2,"ns":0,"title":"B","links":[{"ns":0,"title":"B1"}]}}}}"#,
+            );
+
+        let linked_titles = |client: &WikipediaClient| {
+            pollster::block_on(client.get_many_async(&titles))
+                .into_iter()
+                .map(|(_, body)| {
+                    body.unwrap()
+                        .get_linked_pages()
+                        .unwrap()
+                        .map(|page| page.title())
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            linked_titles(&client(transport.clone())),
+            [vec!["A1", "A2", "A3"], vec!["B1"]]
+        );
+        assert_eq!(transport.requests().len(), 2);
+
+        let capped = WikipediaClient::from_config(
+            WikipediaClientConfig::default()
+                .max_links(Some(1))
+                .transport(transport.clone()),
+        );
+
+        assert_eq!(linked_titles(&capped), [vec!["A1"], vec!["B1"]]);
+        assert_eq!(transport.requests().len(), 4);
+    }
+
+    #[test]
+    fn url_types() {
+        let links_url = "https://en.wikipedia.org/w/api.php?action=query&format=json&prop=links|info&pllimit=500&origin=*&titles=Multekrem";
+
+        let transport = FixtureTransport::new()
+            .with_response(
+                "https://en.wikipedia.org/wiki/Moltekrem",
+                r#"<link rel="canonical" href="https://en.wikipedia.org/wiki/Multekrem"><div id="mw-content-text"><a href="/wiki/Cloudberry">cloudberries</a></div>"#,
+            )
+            .with_response(
+                links_url,
+                r#"{"batchcomplete":"","query":{"pages":{"1":{"pageid":1,"ns":0,"title":"Multekrem","links":[{"ns":0,"title":"Cloudberry"}]}}}}"#,
+            );
+
+        for url_type in [WikipediaUrlType::Basic, WikipediaUrlType::LinksApi] {
+            let client = WikipediaClient::from_config(
+                WikipediaClientConfig::default()
+                    .url_type(url_type)
+                    .transport(transport.clone()),
+            );
+
+            let pathinfo = match url_type {
+                WikipediaUrlType::Basic => "Moltekrem",
+                _ => "Multekrem",
+            };
+
+            let page = pollster::block_on(
+                WikipediaPage::from_title(pathinfo).load_page_text_async(&client),
+            )
+            .unwrap();
+
+            assert_eq!(page.title(), "Multekrem");
+            assert_eq!(
+                page.try_get_linked_pages().unwrap().next().unwrap().title(),
+                "Cloudberry"
+            );
+        }
+    }
+
+    #[test]
+    fn backlinks() {
+        let mut url = Url::parse("https://en.wikipedia.org/w/api.php?action=query&format=json&list=backlinks&blnamespace=0&blfilterredir=nonredirects&bllimit=500&origin=*&bltitle=Multekrem").unwrap();
+
+        let transport = FixtureTransport::new().with_response(
+            url.as_str(),
+            r#"{"continue":{"blcontinue":"0|2","continue":"-||"},"query":{"backlinks":[{"pageid":1,"ns":0,"title":"Cloudberry"}]}}"#,
+        );
+
+        url.query_pairs_mut()
+            .append_pair("blcontinue", "0|2")
+            .append_pair("continue", "-||");
+
+        let transport = transport.with_response(
+            url.as_str(),
+            r#"{"batchcomplete":"","query":{"backlinks":[{"pageid":2,"ns":0,"title":"Norwegian cuisine"}]}}"#,
+        );
+
+        let page = pollster::block_on(
+            WikipediaPage::from_title("Multekrem").load_backlinks_async(&client(transport.clone())),
+        )
+        .unwrap();
+
+        assert_eq!(
+            page.try_get_backlinks()
+                .unwrap()
+                .iter()
+                .map(WikipediaPage::title)
+                .collect::<Vec<_>>(),
+            ["Cloudberry", "Norwegian cuisine"]
+        );
+        assert_eq!(transport.requests().len(), 2);
+
+        // The link limit doesn't apply to backlinks, they have their own
+        let capped = WikipediaClient::from_config(
+            WikipediaClientConfig::default()
+                .max_links(Some(1))
+                .max_backlinks(Some(1))
+                .transport(transport.clone()),
+        );
+
+        let backlinks = |client: &WikipediaClient| {
+            pollster::block_on(client.get_backlinks_async("Multekrem"))
+                .unwrap()
+                .len()
+        };
+
+        assert_eq!(backlinks(&capped), 1);
+        assert_eq!(transport.requests().len(), 3);
+
+        let links_only = WikipediaClient::from_config(
+            WikipediaClientConfig::default()
+                .max_links(Some(1))
+                .transport(transport.clone()),
+        );
+
+        assert_eq!(backlinks(&links_only), 2);
+    }
+
+    #[test]
+    fn summary() {
+        let url = Url::parse("https://en.wikipedia.org/w/api.php?action=query&format=json&prop=extracts|description|pageimages&exintro=1&explaintext=1&piprop=thumbnail&pithumbsize=320&redirects=1&origin=*&titles=Multekrem").unwrap();
+
+        let transport = FixtureTransport::new().with_response(
+            url.as_str(),
+            r#"{"batchcomplete":"","query":{"pages":{"1":{"pageid":1,"ns":0,"title":"Multekrem","extract":"Multekrem is a traditional Norwegian dessert.\n","description":"Norwegian dessert","thumbnail":{"source":"https://upload.wikimedia.org/Moltekrem.jpg","width":320,"height":240}}}}}"#,
+        );
+
+        let page = pollster::block_on(
+            WikipediaPage::from_title("Multekrem").load_summary_async(&client(transport.clone())),
+        )
+        .unwrap();
+
+        let summary = page.try_get_summary().unwrap();
+
+        assert_eq!(
+            summary.extract(),
+            "Multekrem is a traditional Norwegian dessert."
+        );
+        assert_eq!(summary.description(), Some("Norwegian dessert"));
+        assert_eq!(
+            summary.thumbnail().map(Url::as_str),
+            Some("https://upload.wikimedia.org/Moltekrem.jpg")
+        );
+
+        let transport = FixtureTransport::new().with_response(
+            url.as_str(),
+            r#"{"query":{"pages":{"-1":{"ns":0,"title":"Multekrem","missing":""}}}}"#,
+        );
+
+        assert!(matches!(
+            pollster::block_on(client(transport).get_summary_async("Multekrem"))
+                .unwrap_err()
+                .kind(),
+            HttpError::MissingTitle
+        ));
+    }
+
+    #[test]
+    fn summary_then_text() {
+        let url = "https://en.wikipedia.org/w/api.php?action=query&format=json&prop=extracts|description|pageimages&exintro=1&explaintext=1&piprop=thumbnail&pithumbsize=320&redirects=1&origin=*&titles=Multekrem";
+
+        let transport = FixtureTransport::new()
+            .with_response(
+                url,
+                r#"{"query":{"pages":{"1":{"pageid":1,"ns":0,"title":"Multekrem","extract":"Multekrem is a traditional Norwegian dessert."}}}}"#,
+            )
+            .with_response(MULTEKREM_URL, multekrem_page_text());
+
+        let client = client(transport);
+
+        let page =
+            pollster::block_on(WikipediaPage::from_title("Multekrem").load_summary_async(&client))
+                .unwrap();
+
+        // Loading the text replaces the body, not what was loaded before it
+        let page = pollster::block_on(page.load_page_text_async(&client)).unwrap();
+
+        assert_eq!(
+            page.try_get_summary().unwrap().extract(),
+            "Multekrem is a traditional Norwegian dessert."
+        );
+        assert!(page.try_get_body().is_some());
+        assert_eq!(page.title(), "Multekrem");
+    }
+
+    #[test]
+    fn langlinks() {
+        let url = "https://en.wikipedia.org/w/api.php?action=query&format=json&prop=langlinks&lllimit=max&redirects=1&origin=*&titles=Multekrem";
+
+        let mut continued_url = Url::parse(url).unwrap();
+
+        continued_url
+            .query_pairs_mut()
+            .append_pair("continue", "||")
+            .append_pair("llcontinue", "1|no");
+
+        let transport = FixtureTransport::new().with_response(
+            url,
+            r#"{"continue":{"llcontinue":"1|no","continue":"||"},"query":{"pages":{"1":{"pageid":1,"ns":0,"title":"Multekrem","langlinks":[{"lang":"de","*":"Moltebeerencreme"}]}}}}"#,
+        )
+        .with_response(
+            continued_url.as_str(),
+            r#"{"batchcomplete":"","query":{"pages":{"1":{"pageid":1,"ns":0,"title":"Multekrem","langlinks":[{"lang":"no","*":"Multekrem"},{"lang":"not-a-wiki","*":"Multekrem"}]}}}}"#,
+        )
+        .with_response(
+            "https://de.wikipedia.org/w/api.php?origin=*&action=parse&prop=wikitext|revid&format=json&page=Moltebeerencreme",
+            r#"{"parse":{"title":"Moltebeerencreme","pageid":2,"wikitext":{"*":"[[Moltebeere]]"}}}"#,
+        );
+
+        let page = pollster::block_on(
+            WikipediaPage::from_title("Multekrem").load_langlinks_async(&client(transport.clone())),
+        )
+        .unwrap();
+
+        let german = WikiLanguage::from_code("de").unwrap();
+
+        assert_eq!(page.try_get_langlinks().unwrap().len(), 2);
+
+        let moltebeerencreme = page.try_get_langlink(german).unwrap();
+
+        assert_eq!(moltebeerencreme.title(), "Moltebeerencreme");
+        assert_eq!(
+            moltebeerencreme.url().unwrap().as_str(),
+            "https://de.wikipedia.org/wiki/Moltebeerencreme"
+        );
+
+        // The German page is loaded from the German wikipedia, even with an English client
+        let loaded =
+            pollster::block_on(moltebeerencreme.load_page_text_async(&client(transport))).unwrap();
+
+        assert_eq!(loaded.language(), german);
+        assert_eq!(
+            loaded.try_get_linked_pages().unwrap().collect::<Vec<_>>(),
+            [WikipediaPage::from_title("Moltebeere").with_language(german)]
+        );
+    }
+
+    #[test]
+    fn sister_projects() {
+        let transport = FixtureTransport::new()
+            .with_response(
+                "https://en.wiktionary.org/w/api.php?action=query&format=json&list=random&rnnamespace=0&rnlimit=1&origin=*",
+                r#"{"batchcomplete":"","query":{"random":[{"id":1,"ns":0,"title":"waffle"}]}}"#,
+            )
+            .with_response(
+                "https://en.wiktionary.org/w/api.php?origin=*&action=parse&prop=wikitext|revid&format=json&page=waffle",
+                r#"{"parse":{"title":"waffle","pageid":1,"wikitext":{"*":"[[wafel]] [[gaufre]]"}}}"#,
+            );
+
+        let client = WikipediaClient::from_config(
+            WikipediaClientConfig::default()
+                .project(WikiProject::Wiktionary)
+                .transport(transport.clone()),
+        );
+
+        let page = pollster::block_on(client.random_page_async()).unwrap();
+
+        assert_eq!(page.project(), WikiProject::Wiktionary);
+        assert_eq!(
+            page.url().unwrap().as_str(),
+            "https://en.wiktionary.org/wiki/waffle"
+        );
+
+        // The page is loaded from Wiktionary, even with a Wikipedia client
+        let loaded =
+            pollster::block_on(page.load_page_text_async(&self::client(transport))).unwrap();
+
+        assert!(
+            loaded
+                .try_get_linked_pages()
+                .unwrap()
+                .all(|page| page.project() == WikiProject::Wiktionary)
+        );
+    }
+
+    #[test]
+    fn memory_cache() {
+        let transport = FixtureTransport::new()
+            .with_response(MOLTEKREM_URL, MOLTEKREM_REDIRECT)
+            .with_response(MULTEKREM_URL, multekrem_page_text());
+
+        let cache = MemoryCache::new();
+
+        let client = WikipediaClient::from_config(
+            WikipediaClientConfig::default()
+                .cache(cache.clone())
+                .transport(transport.clone()),
+        );
+
+        for _ in 0..2 {
+            assert_eq!(
+                get_blocking(&client, "Moltekrem")
+                    .unwrap()
+                    .get_pathinfo()
+                    .unwrap(),
+                "Multekrem"
+            );
+        }
+
+        assert_eq!(transport.requests().len(), 2);
+        assert_eq!(cache.len(), 2);
+
+        let offline = WikipediaClient::from_config(
+            WikipediaClientConfig::default()
+                .cache(cache)
+                .offline(true)
+                .transport(transport.clone()),
+        );
+
+        assert!(get_blocking(&offline, "multekrem").is_ok());
+        assert!(matches!(
+            get_blocking(&offline, "Waffle").unwrap_err().kind(),
+            HttpError::PageNotFound
+        ));
+        assert!(pollster::block_on(offline.random_page_async()).is_err());
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    #[test]
+    fn batch_cache() {
+        // Missing pages aren't cached, so 'Page 7' is left out
+        let titles: Vec<String> = (0..7).map(|index| format!("Page {index}")).collect();
+
+        let transport =
+            FixtureTransport::new().with_response(batch_url(&titles), batch_body(&titles));
+
+        let cache = MemoryCache::new();
+
+        let client = WikipediaClient::from_config(
+            WikipediaClientConfig::default()
+                .cache(cache.clone())
+                .transport(transport.clone()),
+        );
+
+        let pages: Vec<WikipediaPage> = titles.iter().map(WikipediaPage::from_title).collect();
+
+        for _ in 0..2 {
+            let loaded = pollster::block_on(WikipediaPage::load_many_async(&pages, &client));
+
+            assert_eq!(loaded[3].as_ref().unwrap().redirects(), ["Page 3"]);
+        }
+
+        assert_eq!(transport.requests().len(), 1);
+
+        // Bodies cut down to fewer links are stored apart from the whole bodies
+        let limited = WikipediaClient::from_config(
+            WikipediaClientConfig::default()
+                .cache(cache)
+                .max_links(Some(1))
+                .offline(true)
+                .transport(transport.clone()),
+        );
+
+        let results = pollster::block_on(limited.get_many_async(&titles));
+
+        assert!(results.iter().all(|(_, result)| result.is_err()));
+    }
+
+    #[test]
+    fn directory_cache() {
+        use wikipedia_graph::DirectoryCache;
+
+        let path =
+            std::env::temp_dir().join(format!("wikipedia-graph-cache-test-{}", std::process::id()));
+
+        let transport = FixtureTransport::new().with_response(MULTEKREM_URL, multekrem_page_text());
+
+        let client = WikipediaClient::from_config(
+            WikipediaClientConfig::default()
+                .cache(DirectoryCache::new(&path).unwrap())
+                .transport(transport.clone()),
+        );
+
+        let fetched = get_blocking(&client, "Multekrem").unwrap();
+
+        // A new cache in the same directory, like after restarting the app
+        let offline = WikipediaClient::from_config(
+            WikipediaClientConfig::default()
+                .cache(DirectoryCache::new(&path).unwrap().with_max_entries(1))
+                .offline(true)
+                .transport(transport.clone()),
+        );
+
+        let body = get_blocking(&offline, "Multekrem").unwrap();
+
+        assert_eq!(body.get_pathinfo().unwrap(), "Multekrem");
+        assert_eq!(body.to_string(), fetched.to_string());
+        assert_eq!(transport.requests().len(), 1);
+
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+
+    fn revision_body(title: &str, revid: u64) -> String {
+        format!(
+            r#"{{"parse":{{"title":"{title}","pageid":1,"revid":{revid},"wikitext":{{"*":"[[Cloudberry]]"}}}}}}"#
+        )
+    }
+
+    #[test]
+    fn refresh() {
+        let mut info_url = Url::parse(
+            "https://en.wikipedia.org/w/api.php?action=query&format=json&prop=info&origin=*",
+        )
+        .unwrap();
+
+        info_url.query_pairs_mut().append_pair("titles", "A|B");
+
+        let transport = FixtureTransport::new()
+            .with_response(
+                info_url,
+                r#"{"query":{"pages":{"1":{"pageid":1,"ns":0,"title":"A","lastrevid":10},"2":{"pageid":2,"ns":0,"title":"B","lastrevid":6}}}}"#,
+            )
+            .with_response(raw_url("B"), revision_body("B", 6))
+            .with_headers(raw_url("C"), 304, &[], "")
+            .with_headers(raw_url("D"), 200, &[("etag", "\"d2\"")], revision_body("D", 2));
+
+        let loaded = |title: &str, revid: Option<u64>| {
+            let body = match revid {
+                Some(revid) => revision_body(title, revid),
+                // Bodies cached before revisions were requested
+                None => format!(r#"{{"parse":{{"title":"{title}","wikitext":{{"*":""}}}}}}"#),
+            };
+
+            WikipediaPage::from_title(title)
+                .with_body(WikipediaBody::wikitext_from_text(&body).unwrap())
+        };
+
+        let pages = [
+            loaded("A", Some(10)),
+            loaded("B", Some(5)),
+            loaded("C", None).with_etag("\"c1\""),
+            loaded("D", None),
+        ];
+
+        let report = pollster::block_on(client(transport.clone()).refresh_async(&pages));
+
+        let titles =
+            |pages: &[WikipediaPage]| pages.iter().map(WikipediaPage::title).collect::<Vec<_>>();
+
+        assert_eq!(titles(report.updated()), ["B", "D"]);
+        assert_eq!(titles(report.unchanged()), ["A", "C"]);
+        assert!(report.failed().is_empty());
+
+        assert_eq!(report.updated()[0].revision(), Some(6));
+        assert_eq!(report.updated()[1].etag(), Some("\"d2\""));
+        assert_eq!(transport.requests().len(), 4);
+    }
+}
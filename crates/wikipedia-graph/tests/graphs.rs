@@ -1,12 +1,15 @@
 #[path = "common.rs"]
 mod common;
 
-#[cfg(feature = "petgraph")]
+#[cfg(all(feature = "petgraph", feature = "client"))]
 mod petgraph {
     use crate::common::{self, NUM_LINKED_MULTEKREM_PAGES, multekrem_page};
     use petgraph::prelude::StableDiGraph;
     use pretty_assertions::assert_eq;
-    use wikipedia_graph::{WikipediaGraph, WikipediaPage};
+    use wikipedia_graph::{
+        EdgeKind, FixtureTransport, Link, LinkPlacement, WikiLanguage, WikiProject,
+        WikipediaClient, WikipediaClientConfig, WikipediaGraph, WikipediaPage, WikipediaUrlType,
+    };
 
     #[test]
     fn expand_nodes() {
@@ -16,20 +19,19 @@ mod petgraph {
         let multekrem_index = graph.add_node(multekrem_page());
 
         let connected = graph.try_expand_node(multekrem_index).expect("");
-        connected
-            .iter()
-            .map(|idx| {
-                graph
-                    .node_weight(idx.clone())
-                    .expect("Page expansion returned and invalid index")
-            })
-            .zip(common::multekrem_pages_iter())
-            .for_each(|(known, node)| {
-                assert_eq!(
-                    known.pathinfo().to_lowercase(),
-                    node.pathinfo().to_lowercase()
-                )
-            });
+
+        assert_eq!(
+            connected
+                .iter()
+                .map(|idx| {
+                    graph
+                        .node_weight(*idx)
+                        .expect("Page expansion returned and invalid index")
+                        .clone()
+                })
+                .collect::<Vec<_>>(),
+            common::multekrem_pages_iter().collect::<Vec<_>>()
+        );
     }
 
     #[test]
@@ -62,4 +64,192 @@ mod petgraph {
             .zip(graph_2.node_weights())
             .for_each(|(weight_1, weight_2)| assert_eq!(weight_1.pathinfo(), weight_2.pathinfo()));
     }
+
+    #[test]
+    fn redirected_nodes_are_merged() {
+        let mut graph: StableDiGraph<WikipediaPage, ()> =
+            petgraph::stable_graph::StableDiGraph::default();
+
+        let index = graph.add_node(
+            WikipediaPage::from_title("United States").with_redirects(vec![String::from("USA")]),
+        );
+
+        assert_eq!(
+            graph.node_exists_with_value(&WikipediaPage::from_title("USA")),
+            Some(index)
+        );
+        assert_eq!(
+            graph.node_exists_with_value(&WikipediaPage::from_title("United_States")),
+            Some(index)
+        );
+        assert_eq!(
+            graph.node_exists_with_value(&WikipediaPage::from_title("Canada")),
+            None
+        );
+    }
+
+    #[test]
+    fn expand_incoming() {
+        let mut graph: StableDiGraph<WikipediaPage, ()> =
+            petgraph::stable_graph::StableDiGraph::default();
+
+        let cloudberry_index = graph.add_node(WikipediaPage::from_title("Cloudberry"));
+
+        let index = graph.add_node(multekrem_page().with_backlinks(vec![
+            WikipediaPage::from_title("Cloudberry"),
+            WikipediaPage::from_title("Norwegian cuisine"),
+        ]));
+
+        let added = graph
+            .try_expand_node_incoming(index)
+            .expect("Failed to find node to expand");
+
+        assert_eq!(added.len(), 1);
+        assert_eq!(
+            graph.node_weight(added[0]).unwrap().title(),
+            "Norwegian cuisine"
+        );
+        assert!(graph.contains_edge(cloudberry_index, index));
+        assert!(graph.contains_edge(added[0], index));
+        assert!(!graph.contains_edge(index, cloudberry_index));
+
+        assert!(graph.try_expand_node_incoming(index).unwrap().is_empty());
+        assert_eq!(graph.edge_count(), 2);
+
+        assert!(graph.try_expand_node_incoming(cloudberry_index).is_none());
+    }
+
+    #[test]
+    fn resolved_nodes_are_merged() {
+        let mut graph: StableDiGraph<WikipediaPage, ()> =
+            petgraph::stable_graph::StableDiGraph::default();
+
+        let united_states = graph.add_node(WikipediaPage::from_title("United States"));
+        let usa = graph.add_node(WikipediaPage::from_title("USA"));
+        let waffle = graph.add_node(WikipediaPage::from_title("Waffle"));
+        let nowhere = graph.add_node(WikipediaPage::from_title("Nowhere"));
+
+        graph.add_edge(waffle, usa, ());
+        graph.add_edge(usa, united_states, ());
+        graph.add_edge(waffle, united_states, ());
+        graph.add_edge(usa, nowhere, ());
+
+        let titles: Vec<String> = graph.node_weights().map(WikipediaPage::title).collect();
+
+        let url = WikipediaUrlType::resolve_url(
+            WikiProject::Wikipedia,
+            WikiLanguage::from_code("en").unwrap(),
+            &titles,
+        )
+        .unwrap();
+
+        let transport = FixtureTransport::new().with_response(
+            url,
+            r#"{"batchcomplete":"","query":{"redirects":[{"from":"USA","to":"United States"}],"pages":{"1":{"pageid":1,"ns":0,"title":"United States"},"2":{"pageid":2,"ns":0,"title":"Waffle"},"-1":{"ns":0,"title":"Nowhere","missing":""}}}}"#,
+        );
+
+        let client =
+            WikipediaClient::from_config(WikipediaClientConfig::default().transport(transport));
+
+        let resolved = pollster::block_on(client.resolve_titles_async(&titles)).unwrap();
+
+        assert!(resolved.is_missing("Nowhere"));
+
+        assert_eq!(graph.merge_resolved(&resolved), [usa]);
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.edge_count(), 2);
+        assert!(graph.contains_edge(waffle, united_states));
+        assert!(graph.contains_edge(united_states, nowhere));
+        assert_eq!(graph[united_states].redirects(), ["USA"]);
+        assert_eq!(
+            graph.node_exists_with_value(&WikipediaPage::from_title("USA")),
+            Some(united_states)
+        );
+
+        // The kept node takes the canonical title even if it was added under a redirect
+        let mut graph: StableDiGraph<WikipediaPage, ()> =
+            petgraph::stable_graph::StableDiGraph::default();
+
+        let usa = graph.add_node(WikipediaPage::from_title("USA"));
+        let united_states = graph.add_node(WikipediaPage::from_title("United States"));
+
+        assert_eq!(graph.merge_resolved(&resolved), [united_states]);
+        assert_eq!(graph[usa].title(), "United States");
+        assert_eq!(graph[usa].redirects(), ["USA"]);
+    }
+
+    #[test]
+    fn expand_languages() {
+        let mut graph: StableDiGraph<WikipediaPage, EdgeKind> =
+            petgraph::stable_graph::StableDiGraph::default();
+
+        let german = WikiLanguage::from_code("de").unwrap();
+        let norwegian = WikiLanguage::from_code("no").unwrap();
+
+        let index = graph.add_node(multekrem_page().with_langlinks(vec![
+            WikipediaPage::from_title("Moltebeerencreme").with_language(german),
+            WikipediaPage::from_title("Multekrem").with_language(norwegian),
+        ]));
+
+        let added = graph
+            .try_expand_node_languages(index, &[german])
+            .expect("Failed to find node to expand");
+
+        assert_eq!(added.len(), 1);
+        assert_eq!(graph[added[0]].language(), german);
+        assert!(matches!(
+            graph[graph.find_edge(index, added[0]).unwrap()],
+            EdgeKind::Interlanguage(language) if language == german
+        ));
+
+        let added = graph.try_expand_node_languages(index, &[]).unwrap();
+
+        // The Norwegian page has the same title, but isn't the same page
+        assert_eq!(added.len(), 1);
+        assert_eq!(graph[added[0]].title(), "Multekrem");
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.edge_count(), 2);
+
+        let connected = graph.try_expand_node(index).unwrap();
+
+        assert!(matches!(
+            graph[graph.find_edge(index, connected[0]).unwrap()],
+            EdgeKind::Link(Some(_))
+        ));
+    }
+
+    #[test]
+    fn edges_carry_links() {
+        let mut graph: StableDiGraph<WikipediaPage, Option<Link>> =
+            petgraph::stable_graph::StableDiGraph::default();
+
+        let multekrem_index = graph.add_node(multekrem_page());
+
+        let connected = graph
+            .try_expand_node(multekrem_index)
+            .expect("Failed to find node to expand");
+
+        assert_eq!(connected.len(), NUM_LINKED_MULTEKREM_PAGES);
+
+        let links: Vec<&Link> = connected
+            .iter()
+            .map(|index| {
+                graph
+                    .edge_weight(graph.find_edge(multekrem_index, *index).unwrap())
+                    .unwrap()
+                    .as_ref()
+                    .expect("Expanded edges should carry their link")
+            })
+            .collect();
+
+        assert_eq!(links[0].page().title(), "Norway");
+        assert_eq!(links[0].placement(), Some(LinkPlacement::Infobox));
+        assert_eq!(links[2].display(), Some("Cloudberries"));
+        assert!(
+            links
+                .windows(2)
+                .all(|pair| pair[0].position() < pair[1].position())
+        );
+        assert!(links.iter().any(|link| link.section().is_some()));
+    }
 }
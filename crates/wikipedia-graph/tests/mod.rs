@@ -1,8 +1,13 @@
 mod common;
+#[expect(clippy::duplicate_mod)]
 mod graphs;
 use pretty_assertions::assert_eq;
+use std::collections::HashSet;
 use url::Url;
-use wikipedia_graph::{WikiLanguage, WikipediaPage};
+use wikipedia_graph::{
+    DefaultLinkFilter, Namespace, NamespaceFilter, ParseResponse, SectionFilter, WikiLanguage,
+    WikiLink, WikiProject, WikipediaBody, WikipediaPage, WikipediaUrlError, WikipediaUrlType,
+};
 
 #[test]
 fn page_creation() {
@@ -22,17 +27,315 @@ fn linked_pages() {
         .try_get_linked_pages()
         .expect("Body failed to load (for some reason)");
 
-    let multekrem_linked_pages = common::multekrem_pages_iter();
+    assert_eq!(
+        linked_pages.collect::<Vec<_>>(),
+        common::multekrem_pages_iter().collect::<Vec<_>>()
+    ); // Better error message than itertools::eq
+}
+
+#[test]
+fn page_identity() {
+    let waffle = WikipediaPage::from_title("Waffle");
 
-    linked_pages
+    for spelling in ["waffle", "Waffle_", "%57affle"] {
+        assert_eq!(WikipediaPage::from_title(spelling), waffle);
+    }
+
+    let url = Url::parse("https://wikipedia.org/wiki/belgian_waffle").unwrap();
+
+    assert_eq!(
+        WikipediaPage::try_from_url(url).unwrap(),
+        WikipediaPage::from_title("Belgian waffle")
+    );
+
+    let pages: HashSet<WikipediaPage> = ["waffle", "Waffle", "Belgian waffle"]
         .into_iter()
-        .zip(multekrem_linked_pages)
-        .for_each(|(linked, known_linked)| {
-            assert_eq!(
-                linked.pathinfo().to_lowercase(),
-                known_linked.pathinfo().to_lowercase()
-            )
-        }); // Better error message than itertools::eq
+        .map(WikipediaPage::from_title)
+        .collect();
+
+    assert_eq!(pages.len(), 2);
+    assert!(pages.contains(&WikipediaPage::from_title("belgian_waffle")));
+}
+
+#[test]
+fn page_language() {
+    let german = WikiLanguage::from_code("de").expect("Language code 'de' is invalid");
+
+    let url = Url::parse("https://de.wikipedia.org/wiki/Waffel").unwrap();
+    let page = WikipediaPage::try_from_url(url).unwrap();
+
+    assert_eq!(page.language(), german);
+    assert_eq!(
+        page,
+        WikipediaPage::from_title("Waffel").with_language(german)
+    );
+    assert_ne!(page, WikipediaPage::from_title("Waffel"));
+    assert_eq!(
+        page.url().unwrap().as_str(),
+        "https://de.wikipedia.org/wiki/Waffel"
+    );
+
+    let mobile = Url::parse("https://de.m.wikipedia.org/wiki/Waffel").unwrap();
+
+    assert_eq!(WikipediaPage::try_from_url(mobile).unwrap(), page);
+    assert_eq!(
+        WikipediaPage::from_title("Waffle").language(),
+        WikiLanguage::from_code("en").unwrap()
+    );
+
+    let unknown = Url::parse("https://xx.wikipedia.org/wiki/Waffel").unwrap();
+
+    assert!(matches!(
+        WikipediaPage::try_from_url(unknown),
+        Err(WikipediaUrlError::InvalidLanguage(code)) if code == "xx"
+    ));
+
+    let linked = common::multekrem_page()
+        .with_language(german)
+        .try_get_linked_pages()
+        .expect("Body failed to load")
+        .all(|page| page.language() == german);
+
+    assert!(linked);
+}
+
+#[test]
+fn page_project() {
+    let url = Url::parse("https://fr.wiktionary.org/wiki/gaufre").unwrap();
+    let page = WikipediaPage::try_from_url(url).unwrap();
+
+    assert_eq!(page.project(), WikiProject::Wiktionary);
+    assert_eq!(page.language(), WikiLanguage::from_code("fr").unwrap());
+    assert_eq!(
+        page.url().unwrap().as_str(),
+        "https://fr.wiktionary.org/wiki/gaufre"
+    );
+    assert_eq!(
+        page,
+        WikipediaPage::from_title("gaufre")
+            .with_project(WikiProject::Wiktionary)
+            .with_language(page.language())
+    );
+    assert_ne!(
+        page,
+        WikipediaPage::from_title("Gaufre")
+            .with_project(WikiProject::Wiktionary)
+            .with_language(page.language())
+    );
+    assert_ne!(
+        page,
+        WikipediaPage::from_title("gaufre").with_language(page.language())
+    );
+    assert_eq!(
+        WikipediaPage::from_title("Waffle").project(),
+        WikiProject::Wikipedia
+    );
+
+    let bare = Url::parse("https://wikivoyage.org/wiki/Oslo").unwrap();
+    let page = WikipediaPage::try_from_url(bare).unwrap();
+
+    assert_eq!(page.project(), WikiProject::Wikivoyage);
+    assert_eq!(page.language(), WikiLanguage::English);
+
+    let unknown = Url::parse("https://en.wikiexample.org/wiki/Waffle").unwrap();
+
+    assert!(matches!(
+        WikipediaPage::try_from_url(unknown),
+        Err(WikipediaUrlError::InvalidHost)
+    ));
+
+    let linked = common::multekrem_page()
+        .with_project(WikiProject::Wikiquote)
+        .try_get_linked_pages()
+        .expect("Body failed to load")
+        .all(|page| page.project() == WikiProject::Wikiquote);
+
+    assert!(linked);
+}
+
+#[test]
+fn wikibooks_page() {
+    let url = Url::parse("https://en.wikibooks.org/wiki/Cookbook:Waffles").unwrap();
+    let page = WikipediaPage::try_from_url(url.clone()).unwrap();
+
+    assert_eq!(page.project(), WikiProject::Wikibooks);
+    assert_eq!(page.language(), WikiLanguage::English);
+    assert_eq!(page.url().unwrap(), url);
+
+    // A language with a Wiktionary but no Wikibooks
+    let language = WikiLanguage::from_code("gv").unwrap();
+
+    assert!(WikiProject::Wiktionary.language_code(&language).is_some());
+    assert!(WikiProject::Wikibooks.language_code(&language).is_none());
+}
+
+#[test]
+fn tricky_wikilinks() {
+    let mut response: ParseResponse = serde_json::from_str(&common::multekrem_page_text())
+        .expect("Failed to parse multekrem page");
+
+    response.parse.wikitext.text.push_str(
+        r#"
+<!-- [[Commented out]] --><nowiki>[[Not a link]]</nowiki>
+Served at Christmas.<ref name="xmas">{{cite web|title=[[Christmas]]}}</ref><ref name="xmas" />
+[[File:Multekrem.jpg|thumb|Served with [[krumkake|krumkaker]] and {{lang|no|[[Cloudberry|molte]]}}]]
+Like in [[Star Wars: Episode IV – A New Hope|a film]], see [[Waffle#History|waffles]] and [[#Recipe|below]].
+{{Navbox|list={{flatlist|[[Riskrem]]}}}}
+[[:Category:Desserts]] [[fr:Multekrem]] [[Wayback Machine]]
+"#,
+    );
+
+    let titles: Vec<String> =
+        WikipediaBody::get_linked_pages_from_wikitext(&response, &DefaultLinkFilter)
+            .map(|page| page.title())
+            .collect();
+
+    let mut expected: Vec<String> = common::multekrem_pages_iter()
+        .map(|page| page.title())
+        .collect();
+
+    expected.extend(
+        [
+            "Cloudberry",
+            "Star Wars: Episode IV – A New Hope",
+            "Waffle",
+            "Riskrem",
+        ]
+        .into_iter()
+        .map(String::from),
+    );
+
+    assert_eq!(titles, expected);
+
+    let links = WikipediaBody::get_wikilinks_from_wikitext(&response);
+
+    let waffle = links
+        .iter()
+        .find(|link| link.target() == "Waffle")
+        .expect("Waffle link is missing");
+
+    assert_eq!(waffle.anchor(), Some("History"));
+    assert_eq!(waffle.display(), Some("waffles"));
+
+    let namespaces: Vec<&Namespace> = links.iter().map(WikiLink::namespace).collect();
+
+    assert!(namespaces.contains(&&Namespace::File));
+    assert!(namespaces.contains(&&Namespace::Category));
+    assert!(namespaces.contains(&&Namespace::Interwiki("fr".to_string())));
+
+    let categories: Vec<String> = WikipediaBody::get_linked_pages_from_wikitext(
+        &response,
+        &NamespaceFilter::only([Namespace::Category]),
+    )
+    .map(|page| page.title())
+    .collect();
+
+    assert_eq!(
+        categories,
+        [
+            "Category:Norwegian desserts",
+            "Category:Fruit desserts",
+            "Category:Desserts"
+        ]
+    );
+}
+
+#[test]
+fn interwiki_links() {
+    let mut response: ParseResponse = serde_json::from_str(&common::multekrem_page_text())
+        .expect("Failed to parse multekrem page");
+
+    response
+        .parse
+        .wikitext
+        .text
+        .push_str("[[fr:Crème de plaquebière]] [[wikt:multekrem]]");
+
+    let german = WikiLanguage::from_code("de").unwrap();
+
+    let page = WikipediaPage::from_title("Multekrem")
+        .with_language(german)
+        .with_body(WikipediaBody::WikiText(response));
+
+    let filter = NamespaceFilter::all().with_interwiki(true);
+
+    let interwiki: Vec<WikipediaPage> = page
+        .try_get_links_with(&filter)
+        .expect("Body failed to load")
+        .into_iter()
+        .map(|link| link.page().clone())
+        .filter(|page| page.language() != german)
+        .collect();
+
+    // The French link leads to the French wiki, the Wiktionary link can't be followed
+    assert_eq!(
+        interwiki,
+        [WikipediaPage::from_title("Crème de plaquebière")
+            .with_language(WikiLanguage::from_code("fr").unwrap())]
+    );
+
+    assert!(
+        page.try_get_linked_pages_with(&filter)
+            .expect("Body failed to load")
+            .all(|page| !matches!(page.namespace(), Namespace::Interwiki(_)))
+    );
+}
+
+#[test]
+fn api_namespaces() {
+    let response = serde_json::from_str(
+        r#"{"query":{"pages":{"1":{"pageid":1,"ns":0,"title":"Multekrem","links":[
+            {"ns":0,"title":"Moltebeere"},{"ns":14,"title":"Kategorie:Dessert"}]}}}}"#,
+    )
+    .unwrap();
+
+    let namespaces: Vec<Namespace> = WikipediaBody::Links(response)
+        .get_linked_pages_with(&NamespaceFilter::all())
+        .expect("Body has no links")
+        .map(|page| page.namespace())
+        .collect();
+
+    // 'Kategorie' isn't a known prefix, but the API knows the namespace
+    assert_eq!(namespaces, [Namespace::Main, Namespace::Category]);
+}
+
+#[test]
+fn section_scoped_links() {
+    let page = common::multekrem_page();
+
+    let lead: Vec<String> = page
+        .try_get_linked_pages_with(&SectionFilter::lead())
+        .expect("Body failed to load")
+        .map(|page| page.title())
+        .collect();
+
+    // Every linked page except the list under "See also"
+    let expected: Vec<String> = common::multekrem_pages_iter()
+        .take(common::NUM_LINKED_MULTEKREM_PAGES - 1)
+        .map(|page| page.title())
+        .collect();
+
+    assert_eq!(lead, expected);
+
+    let see_also: Vec<String> = page
+        .try_get_linked_pages_with(&SectionFilter::sections(["see also"]))
+        .expect("Body failed to load")
+        .map(|page| page.title())
+        .collect();
+
+    assert_eq!(see_also, ["List of Norwegian desserts"]);
+
+    let response: ParseResponse = serde_json::from_str(&common::multekrem_page_text())
+        .expect("Failed to parse multekrem page");
+
+    let see_also: Vec<String> = WikipediaBody::get_linked_pages_from_wikitext(
+        &response,
+        &SectionFilter::sections(["see also"]),
+    )
+    .map(|page| page.title())
+    .collect();
+
+    assert_eq!(see_also, ["List of Norwegian desserts"]);
 }
 
 #[test]
@@ -64,3 +367,44 @@ fn page_is_loaded() {
 
     assert!(!page.is_page_text_loaded());
 }
+
+#[test]
+fn html_body() {
+    let html = r#"<html><head><link rel="canonical" href="https://en.wikipedia.org/wiki/Multekrem"></head>
+<body><a href="/wiki/Main_Page">Main page</a>
+<div id="mw-content-text"><p><a href="/wiki/Cloudberry" title="Cloudberry">cloudberries</a>
+<a href="/wiki/Whipped_cream#History" title="Whipped cream">cream</a>
+<a href="/wiki/File:Multekrem.jpg" class="mw-file-description"></a>
+<a href="/wiki/Cloudberry" title="Cloudberry">again</a>
+<a href="/wiki/Star_Wars:_Andor" title="Star Wars: Andor">a show</a>
+<a href="https://example.com/wiki/Elsewhere">elsewhere</a></p></div></body></html>"#;
+
+    let body = WikipediaBody::from_url_type(WikipediaUrlType::Basic, html.to_string())
+        .expect("HTML bodies can't fail to parse");
+
+    assert_eq!(body.get_pathinfo().unwrap(), "Multekrem");
+    assert!(body.redirects_to().is_none());
+    assert_eq!(
+        body.get_linked_pages()
+            .expect("Fixture has no article content")
+            .map(|page| page.title())
+            .collect::<Vec<_>>(),
+        ["Cloudberry", "Whipped cream", "Star Wars: Andor"]
+    );
+    assert_eq!(
+        body.get_linked_pages_with(&NamespaceFilter::only([Namespace::File]))
+            .expect("Fixture has no article content")
+            .map(|page| page.namespace())
+            .collect::<Vec<_>>(),
+        [Namespace::File]
+    );
+
+    let encoded = WikipediaBody::from_url_type(
+        WikipediaUrlType::Basic,
+        r#"<link rel="canonical" href="https://fr.wikipedia.org/wiki/Cr%C3%A8me_fouett%C3%A9e">"#
+            .to_string(),
+    )
+    .expect("HTML bodies can't fail to parse");
+
+    assert_eq!(encoded.get_pathinfo().unwrap(), "Crème_fouettée");
+}